@@ -0,0 +1,34 @@
+//! Measures ply generation with [`api::Game::perft`] on well-known positions,
+//! so only generating, making and taking back plys is timed.
+//!
+//! Run with `cargo bench -p api --bench ply_gen`,
+//! see `benches/README.md` for recorded numbers.
+
+use std::time::{Duration, Instant};
+
+const POSITIONS: [(&str, &str, u32); 3] = [
+    ("start", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 4),
+    ("kiwipete", "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 3),
+    ("endgame", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 5),
+];
+
+const ITERATIONS: u32 = 5;
+
+fn main() {
+    for (name, fen, depth) in POSITIONS {
+        let game = api::fen_game(fen).unwrap();
+        let mut total = Duration::ZERO;
+        let mut nodes = 0;
+
+        for _ in 0..ITERATIONS {
+            let start = Instant::now();
+            nodes = game.perft(depth).unwrap();
+            total += start.elapsed();
+        }
+
+        let mean = total / ITERATIONS;
+        let rate = nodes as f64 / mean.as_secs_f64();
+
+        println!("{name:<10} depth {depth}: {nodes:>7} nodes in {mean:>10.2?} ({rate:>10.0} nodes/s)");
+    }
+}
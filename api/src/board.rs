@@ -41,24 +41,47 @@ macro_rules! out_of_bounds {
 
 pub(crate) use tile;
 
+mod bitboard;
+pub(crate) use bitboard::*;
+
+mod zobrist;
+pub(crate) use zobrist::*;
+
+mod square;
+pub use square::*;
+
+mod piece;
+pub use piece::*;
+
 const BOARD_WIDTH: usize = 10;
 const BOARD_HEIGHT: usize = 12;
 
 /// Represents an error occuring in the back-end board representation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BoardError {
+    /// An index, with its position, that is not a square on the board.
     OutOfBounds(Pos, usize)
 }
 
 impl std::fmt::Display for BoardError {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(formatter, "invalid board operation")
+        match self {
+            BoardError::OutOfBounds(pos, index) => write!(
+                formatter, 
+                "invalid board operation: index {index} (rank {}, file {}) is not a square on the board", 
+                pos.rank, 
+                pos.file,
+            ),
+        }
     }
 }
 
+impl std::error::Error for BoardError {}
+
 /// Represents both piece colors (see [`Tile`]) and player colors (see [`Game`]).
 /// 
 /// [`Game`]: `super::Game`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Color {
     White,
@@ -81,6 +104,7 @@ impl std::fmt::Display for Color {
 /// Note, sentinel tiles are only used internally. 
 /// 
 /// All pieces have a [`Color`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Tile {
     Pawn(Color),
@@ -119,6 +143,7 @@ impl std::fmt::Display for Tile {
 /// Represents zero-indexed positions on the board.
 /// 
 /// Positions are used commonly used when interfacing with the user.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Pos {
     pub rank: i32,
@@ -126,10 +151,31 @@ pub struct Pos {
 }
 
 
+/// Represents the tiles overwritten when a ply is made on the board (see [`Board::make_ply`]),
+/// so that the ply can be unmade without copying the board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BoardUndo {
+    changes: [(usize, Tile); 4],
+    len: usize,
+}
+
+impl BoardUndo {
+    /// Returns the overwritten tiles as indices and the tiles they held, in the order they were written.
+    pub(crate) fn changes(&self) -> &[(usize, Tile)] {
+        &self.changes[..self.len]
+    }
+}
+
 /// Represents the chessboard as a one dimensional array.
-#[derive(Debug, Clone)]
+/// 
+/// Alongside the tiles, the board keeps one bitboard, a 64-bit set of tiles, per piece type and color,
+/// used for fast ply generation and attack tests, and the Zobrist hash of the pieces.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Board {
     tiles: [Tile; BOARD_WIDTH * BOARD_HEIGHT],
+    pieces: [[Bitboard; 6]; 2],
+    occupancy: [Bitboard; 2],
+    hash: u64,
 }
 
 impl Board {
@@ -141,11 +187,11 @@ impl Board {
     /// Sets a tile value at a given index, 
     /// or produces an error if the index is out of bounds, or the tile is a sentinel value.
     pub(crate) fn set_tile(&mut self, index: usize, piece: Tile) -> Result<(), BoardError> {
-        match self.tiles.get_mut(index) {
-            Some(tile) => match piece {
+        match self.tiles.get(index) {
+            Some(tile) => match (tile, piece) {
                 // Sentinel tiles cannot be set.
-                Tile::Sentinel => Err(out_of_bounds!(index)),
-                _ => { *tile = piece; Ok(()) },
+                (Tile::Sentinel, _) | (_, Tile::Sentinel) => Err(out_of_bounds!(index)),
+                _ => { self.put(index, piece); Ok(()) },
             },
             None => Err(out_of_bounds!(index)),
         }
@@ -154,13 +200,12 @@ impl Board {
     /// Sets a tile to empty and returns the removed tile, 
     /// or produces an error if the index is out of bounds or the tile is a sentinel value.
     pub(crate) fn rem_tile(&mut self, index: usize) -> Result<Tile, BoardError> {
-        match self.tiles.get_mut(index) {
-            Some(tile) => match tile {
+        match self.tiles.get(index) {
+            Some(tile) => match *tile {
                 // Sentinel tiles cannot be removed.
                 Tile::Sentinel => Err(out_of_bounds!(index)),
-                _ => { 
-                    let val = tile.clone();
-                    *tile = Tile::Empty; 
+                val => { 
+                    self.put(index, Tile::Empty);
                     Ok(val)
                 },
             },
@@ -173,14 +218,133 @@ impl Board {
         &self.tiles
     }
 
+    /// Moves a piece, promoting it if a promotion is given, and returns the record needed to unmake the ply.
+    /// 
+    /// Castling plys are described by the king's movement, the rook is relocated here.
+    /// A pawn moving to the en passant target also removes the pawn it captures.
+    pub(crate) fn make_ply(&mut self, origin: usize, destination: usize, promotion: Option<Tile>, en_passant: Option<usize>) -> Result<BoardUndo, BoardError> {
+        let mut undo = BoardUndo { changes: [(0, tile!(.)); 4], len: 0 };
+
+        let tile = self.replace_tile(origin, tile!(.), &mut undo)?;
+        self.replace_tile(destination, promotion.unwrap_or(tile), &mut undo)?;
+
+        match tile {
+            // En passant, the captured pawn is not on the destination tile.
+            tile!(P) if Some(destination) == en_passant => {
+                self.replace_tile(destination - 10, tile!(.), &mut undo)?;
+            },
+            tile!(p) if Some(destination) == en_passant => {
+                self.replace_tile(destination + 10, tile!(.), &mut undo)?;
+            },
+            // Castling.
+            tile!(K) | tile!(k) if destination == origin + 2 => {
+                let rook = self.replace_tile(origin + 3, tile!(.), &mut undo)?;
+                self.replace_tile(origin + 1, rook, &mut undo)?;
+            },
+            tile!(K) | tile!(k) if destination + 2 == origin => {
+                let rook = self.replace_tile(origin - 4, tile!(.), &mut undo)?;
+                self.replace_tile(origin - 1, rook, &mut undo)?;
+            },
+            _ => (),
+        }
+
+        Ok(undo)
+    }
+
+    /// Restores the tiles overwritten by [`Board::make_ply`].
+    /// 
+    /// Plys must be unmade in the reverse order they were made.
+    pub(crate) fn unmake_ply(&mut self, undo: &BoardUndo) {
+        for &(index, tile) in undo.changes[..undo.len].iter().rev() {
+            self.put(index, tile);
+        }
+    }
+
+    /// Writes a piece or empty tile to a playable index, 
+    /// keeping the bitboards and the hash in step with the tiles.
+    fn put(&mut self, index: usize, piece: Tile) {
+        let square = square_of(index);
+        let bit: Bitboard = 1 << square;
+
+        if let Some((color, kind)) = Self::slot(self.tiles[index]) {
+            self.pieces[color][kind] &= !bit;
+            self.occupancy[color] &= !bit;
+            self.hash ^= piece_key(color, kind, square);
+        }
+
+        if let Some((color, kind)) = Self::slot(piece) {
+            self.pieces[color][kind] |= bit;
+            self.occupancy[color] |= bit;
+            self.hash ^= piece_key(color, kind, square);
+        }
+
+        self.tiles[index] = piece;
+    }
+
+    /// Returns the color and piece type a tile is stored under in the bitboards, if it holds a piece.
+    fn slot(tile: Tile) -> Option<(usize, usize)> {
+        let (color, kind) = match tile {
+            Tile::Pawn(color) => (color, 0),
+            Tile::Knight(color) => (color, 1),
+            Tile::Bishop(color) => (color, 2),
+            Tile::Rook(color) => (color, 3),
+            Tile::Queen(color) => (color, 4),
+            Tile::King(color) => (color, 5),
+            Tile::Empty | Tile::Sentinel => return None,
+        };
+
+        Some((color as usize, kind))
+    }
+
+    /// Returns the bitboard of all pieces matching a tile, or an empty bitboard for empty and sentinel tiles.
+    pub(crate) fn bitboard(&self, tile: Tile) -> Bitboard {
+        match Self::slot(tile) {
+            Some((color, kind)) => self.pieces[color][kind],
+            None => 0,
+        }
+    }
+
+    /// Returns the bitboard of all pieces of a color.
+    pub(crate) fn occupancy(&self, color: Color) -> Bitboard {
+        self.occupancy[color as usize]
+    }
+
+    /// Returns the bitboard of all pieces on the board.
+    pub(crate) fn occupied(&self) -> Bitboard {
+        self.occupancy[0] | self.occupancy[1]
+    }
+
+    /// Returns the Zobrist hash of the pieces on the board, updated with every tile write.
+    pub(crate) fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Sets a tile and records its previous value in an undo record.
+    fn replace_tile(&mut self, index: usize, piece: Tile, undo: &mut BoardUndo) -> Result<Tile, BoardError> {
+        let previous = match self.tiles.get(index) {
+            Some(tile) => *tile,
+            None => return Err(out_of_bounds!(index)),
+        };
+
+        self.set_tile(index, piece)?;
+
+        undo.changes[undo.len] = (index, previous);
+        undo.len += 1;
+
+        Ok(previous)
+    }
+
     /// Creates an empty board surrounded by sentitnel tiles.
     pub(crate) fn new() -> Self {
         let mut board = Self {
-            tiles: [tile!(.); BOARD_WIDTH * BOARD_HEIGHT]
+            tiles: [tile!(.); BOARD_WIDTH * BOARD_HEIGHT],
+            pieces: [[0; 6]; 2],
+            occupancy: [0; 2],
+            hash: 0,
         };
 
         for (index, tile) in board.tiles.iter_mut().enumerate() {
-            if (index < 20) | (index >= 100) | (index % 10 == 0) | (index % 10 == 9) {
+            if !(20..100).contains(&index) | (index % 10 == 0) | (index % 10 == 9) {
                 *tile = tile!(_);
             }
         }
@@ -190,7 +354,38 @@ impl Board {
 
     /// Clears the board of any lingering pieces.
     pub(crate) fn empty(&mut self) {
-        self.tiles = Self::new().tiles;
+        *self = Self::new();
+    }
+
+    /// Returns true if neither player has the pieces required to checkmate.
+    /// 
+    /// This is the case with only kings, a king and a single minor piece against a lone king, 
+    /// or any number of bishops where all bishops stand on tiles of the same color.
+    pub(crate) fn is_insufficient_material(&self) -> bool {
+        let mut knights = 0;
+        let mut light_bishops = 0;
+        let mut dark_bishops = 0;
+
+        for (index, tile) in self.tiles.iter().enumerate() {
+            match tile {
+                tile!(P) | tile!(p) | tile!(R) | tile!(r) | tile!(Q) | tile!(q) => return false,
+                tile!(N) | tile!(n) => knights += 1,
+                tile!(B) | tile!(b) => {
+                    let pos = Self::get_pos(index);
+                    match (pos.rank + pos.file) % 2 {
+                        0 => dark_bishops += 1,
+                        _ => light_bishops += 1,
+                    }
+                },
+                _ => (),
+            }
+        }
+
+        match knights {
+            0 => light_bishops == 0 || dark_bishops == 0,
+            1 => light_bishops + dark_bishops == 0,
+            _ => false,
+        }
     }
 
     /// Returns the tile index for a given position.
@@ -285,4 +480,61 @@ mod tests {
 
         assert_eq!(*board.get_tiles(), default_tiles);
     }
+
+    #[test]
+    fn make_ply() {
+        let mut board = Board::new();
+
+        board.set_tile(25, tile!(K)).unwrap();
+        board.set_tile(28, tile!(R)).unwrap();
+        board.set_tile(65, tile!(P)).unwrap();
+        board.set_tile(66, tile!(p)).unwrap();
+
+        let original = board.clone();
+
+        // Castling king side relocates the rook.
+        let undo = board.make_ply(25, 27, None, None).unwrap();
+        assert_eq!(board.get_tile(27), Some(&tile!(K)));
+        assert_eq!(board.get_tile(26), Some(&tile!(R)));
+        assert_eq!(board.get_tile(28), Some(&tile!(.)));
+
+        board.unmake_ply(&undo);
+        assert_eq!(board, original);
+
+        // En passant removes the pawn behind the destination.
+        let undo = board.make_ply(65, 76, None, Some(76)).unwrap();
+        assert_eq!(board.get_tile(76), Some(&tile!(P)));
+        assert_eq!(board.get_tile(66), Some(&tile!(.)));
+
+        board.unmake_ply(&undo);
+        assert_eq!(board, original);
+    }
+
+    #[test]
+    fn insufficient_material() {
+        let mut board = Board::new();
+
+        board.set_tile(25, tile!(K)).unwrap();
+        board.set_tile(95, tile!(k)).unwrap();
+        assert!(board.is_insufficient_material());
+
+        board.set_tile(23, tile!(N)).unwrap();
+        assert!(board.is_insufficient_material());
+
+        // Bishops on c1 and f8 stand on dark tiles.
+        board.set_tile(23, tile!(B)).unwrap();
+        board.set_tile(96, tile!(b)).unwrap();
+        assert!(board.is_insufficient_material());
+
+        // The bishop on c8 stands on a light tile.
+        board.set_tile(96, tile!(.)).unwrap();
+        board.set_tile(93, tile!(b)).unwrap();
+        assert!(!board.is_insufficient_material());
+
+        board.set_tile(93, tile!(n)).unwrap();
+        assert!(!board.is_insufficient_material());
+
+        board.set_tile(93, tile!(p)).unwrap();
+        assert!(!board.is_insufficient_material());
+    }
 }
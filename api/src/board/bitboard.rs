@@ -0,0 +1,174 @@
+//! Bitboards are 64-bit sets of tiles, one bit per square from a1 (bit 0) to h8 (bit 63).
+//! 
+//! Attacks of knights, kings and pawns are looked up in precomputed tables,
+//! attacks of sliding pieces are found by cutting precomputed rays at the first blocker.
+
+/// Represents a set of tiles, where bit `rank * 8 + file` is set for each tile in the set.
+pub(crate) type Bitboard = u64;
+
+/// Returns the square (0 to 63) of a mailbox index (see [`Board`]).
+/// 
+/// [`Board`]: `super::Board`
+pub(crate) const fn square_of(index: usize) -> usize {
+    (index / 10 - 2) * 8 + index % 10 - 1
+}
+
+/// Returns the mailbox index (see [`Board`]) of a square (0 to 63).
+/// 
+/// [`Board`]: `super::Board`
+pub(crate) const fn index_of(square: usize) -> usize {
+    21 + square % 8 + square / 8 * 10
+}
+
+/// Iterates over the squares of a bitboard, from a1 towards h8.
+pub(crate) struct Squares(pub(crate) Bitboard);
+
+impl Iterator for Squares {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let square = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+
+        Some(square)
+    }
+}
+
+/// Returns the set of squares reached from each square by the given (rank, file) steps, taken once.
+const fn gen_step_attacks(steps: &[(i32, i32)]) -> [Bitboard; 64] {
+    let mut attacks = [0; 64];
+
+    let mut square = 0;
+    while square < 64 {
+        let mut i = 0;
+        while i < steps.len() {
+            let rank = (square / 8) as i32 + steps[i].0;
+            let file = (square % 8) as i32 + steps[i].1;
+
+            if rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+                attacks[square] |= 1 << (rank * 8 + file);
+            }
+
+            i += 1;
+        }
+
+        square += 1;
+    }
+
+    attacks
+}
+
+/// Returns the squares passed by a ray from each square in the given (rank, file) direction,
+/// up to the edge of the board.
+const fn gen_rays(direction: (i32, i32)) -> [Bitboard; 64] {
+    let mut rays = [0; 64];
+
+    let mut square = 0;
+    while square < 64 {
+        let mut rank = (square / 8) as i32 + direction.0;
+        let mut file = (square % 8) as i32 + direction.1;
+
+        while rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+            rays[square] |= 1 << (rank * 8 + file);
+            rank += direction.0;
+            file += direction.1;
+        }
+
+        square += 1;
+    }
+
+    rays
+}
+
+pub(crate) const KNIGHT_ATTACKS: [Bitboard; 64] = gen_step_attacks(&[
+    (-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1),
+]);
+
+pub(crate) const KING_ATTACKS: [Bitboard; 64] = gen_step_attacks(&[
+    (-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1),
+]);
+
+/// Pawn attacks indexed by color, white first.
+pub(crate) const PAWN_ATTACKS: [[Bitboard; 64]; 2] = [
+    gen_step_attacks(&[(1, -1), (1, 1)]),
+    gen_step_attacks(&[(-1, -1), (-1, 1)]),
+];
+
+/// Rays in directions where the square number increases, the first blocker is the lowest bit.
+/// 
+/// Ordered north, east, north east and north west, each opposite the ray of the same line in [`NEGATIVE_RAYS`].
+const POSITIVE_RAYS: [[Bitboard; 64]; 4] = [
+    gen_rays((1, 0)), gen_rays((0, 1)), gen_rays((1, 1)), gen_rays((1, -1)),
+];
+
+/// Rays in directions where the square number decreases, the first blocker is the highest bit.
+/// 
+/// Ordered south, west, south west and south east.
+const NEGATIVE_RAYS: [[Bitboard; 64]; 4] = [
+    gen_rays((-1, 0)), gen_rays((0, -1)), gen_rays((-1, -1)), gen_rays((-1, 1)),
+];
+
+/// Returns the squares a slider attacks along both directions of a line,
+/// up to and including the first occupied square in each direction.
+fn line_attacks(square: usize, occupied: Bitboard, line: usize) -> Bitboard {
+    let mut positive = POSITIVE_RAYS[line][square];
+    let blockers = positive & occupied;
+    if blockers != 0 {
+        positive ^= POSITIVE_RAYS[line][blockers.trailing_zeros() as usize];
+    }
+
+    let mut negative = NEGATIVE_RAYS[line][square];
+    let blockers = negative & occupied;
+    if blockers != 0 {
+        negative ^= NEGATIVE_RAYS[line][63 - blockers.leading_zeros() as usize];
+    }
+
+    positive | negative
+}
+
+/// Returns the squares a rook on the given square attacks.
+pub(crate) fn rook_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    line_attacks(square, occupied, 0) | line_attacks(square, occupied, 1)
+}
+
+/// Returns the squares a bishop on the given square attacks.
+pub(crate) fn bishop_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    line_attacks(square, occupied, 2) | line_attacks(square, occupied, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squares() {
+        assert_eq!(square_of(21), 0);
+        assert_eq!(square_of(98), 63);
+        assert_eq!(index_of(12), 35);
+        assert!((0..64).all(|square| square_of(index_of(square)) == square));
+
+        assert_eq!(Squares(0b1010_0001).collect::<Vec<_>>(), vec![0, 5, 7]);
+    }
+
+    #[test]
+    fn attacks() {
+        // Knight on a1 reaches b3 and c2, king on e1 reaches d1, d2, e2, f2 and f1.
+        assert_eq!(KNIGHT_ATTACKS[0], 1 << 17 | 1 << 10);
+        assert_eq!(KING_ATTACKS[4], 1 << 3 | 1 << 11 | 1 << 12 | 1 << 13 | 1 << 5);
+        assert_eq!(PAWN_ATTACKS[0][8], 1 << 17);
+        assert_eq!(PAWN_ATTACKS[1][52], 1 << 43 | 1 << 45);
+
+        // Rook on a1 blocked by a3 and c1.
+        let occupied = 1 << 16 | 1 << 2;
+        assert_eq!(rook_attacks(0, occupied), 1 << 8 | 1 << 16 | 1 << 1 | 1 << 2);
+
+        // Bishop on d4 blocked by f6, on an otherwise empty board.
+        let attacks = bishop_attacks(27, 1 << 45);
+        assert_eq!(attacks.count_ones(), 11);
+        assert_eq!(attacks & 1 << 54, 0);
+    }
+}
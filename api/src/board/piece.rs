@@ -0,0 +1,144 @@
+use super::{Color, Tile};
+
+/// Represents the six types of pieces, without a color (see [`Piece`]).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl PieceKind {
+    /// All piece types, from pawn to king.
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::Pawn,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Rook,
+        PieceKind::Queen,
+        PieceKind::King,
+    ];
+
+    /// Returns the conventional material value of the piece type,
+    /// 1 for pawns, 3 for knights and bishops, 5 for rooks, 9 for queens and 0 for kings.
+    pub fn value(&self) -> u32 {
+        match self {
+            PieceKind::Pawn => 1,
+            PieceKind::Knight | PieceKind::Bishop => 3,
+            PieceKind::Rook => 5,
+            PieceKind::Queen => 9,
+            PieceKind::King => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for PieceKind {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let string = match self {
+            PieceKind::Pawn => "Pawn",
+            PieceKind::Knight => "Knight",
+            PieceKind::Bishop => "Bishop",
+            PieceKind::Rook => "Rook",
+            PieceKind::Queen => "Queen",
+            PieceKind::King => "King",
+        };
+
+        write!(formatter, "{string}")
+    }
+}
+
+/// Represents a piece on the board, a [`PieceKind`] with a [`Color`].
+/// 
+/// Pieces convert to and from tiles (see [`Tile`]), where empty and sentinel tiles have no piece.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Piece {
+    pub color: Color,
+    pub kind: PieceKind,
+}
+
+impl Piece {
+    /// Constructs a piece of a color and type.
+    pub fn new(color: Color, kind: PieceKind) -> Self {
+        Self { color, kind }
+    }
+}
+
+impl std::fmt::Display for Piece {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "{} {}", self.color, self.kind)
+    }
+}
+
+impl Tile {
+    /// Returns the piece on the tile, or None for empty and sentinel tiles.
+    pub fn piece(&self) -> Option<Piece> {
+        let (color, kind) = match *self {
+            Tile::Pawn(color) => (color, PieceKind::Pawn),
+            Tile::Knight(color) => (color, PieceKind::Knight),
+            Tile::Bishop(color) => (color, PieceKind::Bishop),
+            Tile::Rook(color) => (color, PieceKind::Rook),
+            Tile::Queen(color) => (color, PieceKind::Queen),
+            Tile::King(color) => (color, PieceKind::King),
+            Tile::Empty | Tile::Sentinel => return None,
+        };
+
+        Some(Piece { color, kind })
+    }
+}
+
+impl From<Piece> for Tile {
+    fn from(piece: Piece) -> Self {
+        match piece.kind {
+            PieceKind::Pawn => Tile::Pawn(piece.color),
+            PieceKind::Knight => Tile::Knight(piece.color),
+            PieceKind::Bishop => Tile::Bishop(piece.color),
+            PieceKind::Rook => Tile::Rook(piece.color),
+            PieceKind::Queen => Tile::Queen(piece.color),
+            PieceKind::King => Tile::King(piece.color),
+        }
+    }
+}
+
+impl TryFrom<Tile> for Piece {
+    type Error = Tile;
+
+    /// Returns the piece on a tile, or the tile itself if it is empty or a sentinel.
+    fn try_from(tile: Tile) -> Result<Self, Self::Error> {
+        tile.piece().ok_or(tile)
+    }
+}
+
+impl TryFrom<Tile> for PieceKind {
+    type Error = Tile;
+
+    /// Returns the type of the piece on a tile, or the tile itself if it is empty or a sentinel.
+    fn try_from(tile: Tile) -> Result<Self, Self::Error> {
+        tile.piece().map(|piece| piece.kind).ok_or(tile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        for color in [Color::White, Color::Black] {
+            for kind in PieceKind::ALL {
+                let piece = Piece::new(color, kind);
+
+                assert_eq!(Tile::from(piece).piece(), Some(piece));
+                assert_eq!(PieceKind::try_from(Tile::from(piece)), Ok(kind));
+            }
+        }
+
+        assert_eq!(Piece::try_from(Tile::Empty), Err(Tile::Empty));
+        assert_eq!(Tile::Sentinel.piece(), None);
+        assert_eq!(Piece::new(Color::Black, PieceKind::Knight).to_string(), "Black Knight");
+    }
+}
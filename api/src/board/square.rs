@@ -0,0 +1,189 @@
+use super::{index_of, square_of, Pos};
+
+/// Represents one of the 64 squares of the board, from a1 to h8.
+/// 
+/// Squares are ordered rank by rank, a1, b1 and so on up to h8,
+/// and are written in lowercase algebraic notation (e4 etc.).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Square {
+    A1, B1, C1, D1, E1, F1, G1, H1,
+    A2, B2, C2, D2, E2, F2, G2, H2,
+    A3, B3, C3, D3, E3, F3, G3, H3,
+    A4, B4, C4, D4, E4, F4, G4, H4,
+    A5, B5, C5, D5, E5, F5, G5, H5,
+    A6, B6, C6, D6, E6, F6, G6, H6,
+    A7, B7, C7, D7, E7, F7, G7, H7,
+    A8, B8, C8, D8, E8, F8, G8, H8,
+}
+
+/// Represents an error when converting a position or a string to a [`Square`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SquareError {
+    OutOfBounds(Pos),
+    InvalidString(String),
+}
+
+impl std::fmt::Display for SquareError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SquareError::OutOfBounds(pos) => write!(formatter, "no square at rank {} and file {}", pos.rank, pos.file),
+            SquareError::InvalidString(string) => write!(formatter, "invalid square '{string}'"),
+        }
+    }
+}
+
+impl std::error::Error for SquareError {}
+
+impl Square {
+    /// All squares in order, a1, b1 and so on up to h8.
+    pub const ALL: [Square; 64] = [
+        Square::A1, Square::B1, Square::C1, Square::D1, Square::E1, Square::F1, Square::G1, Square::H1,
+        Square::A2, Square::B2, Square::C2, Square::D2, Square::E2, Square::F2, Square::G2, Square::H2,
+        Square::A3, Square::B3, Square::C3, Square::D3, Square::E3, Square::F3, Square::G3, Square::H3,
+        Square::A4, Square::B4, Square::C4, Square::D4, Square::E4, Square::F4, Square::G4, Square::H4,
+        Square::A5, Square::B5, Square::C5, Square::D5, Square::E5, Square::F5, Square::G5, Square::H5,
+        Square::A6, Square::B6, Square::C6, Square::D6, Square::E6, Square::F6, Square::G6, Square::H6,
+        Square::A7, Square::B7, Square::C7, Square::D7, Square::E7, Square::F7, Square::G7, Square::H7,
+        Square::A8, Square::B8, Square::C8, Square::D8, Square::E8, Square::F8, Square::G8, Square::H8,
+    ];
+
+    /// Returns the square on a zero-indexed rank and file, or None if either is outside 0 to 7.
+    pub fn new(rank: i32, file: i32) -> Option<Square> {
+        match (0..8).contains(&rank) && (0..8).contains(&file) {
+            true => Some(Self::ALL[(rank * 8 + file) as usize]),
+            false => None,
+        }
+    }
+
+    /// Returns the square with the given index, from 0 for a1 to 63 for h8.
+    pub fn from_index(index: usize) -> Option<Square> {
+        Self::ALL.get(index).copied()
+    }
+
+    /// Returns the index of the square, from 0 for a1 to 63 for h8.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the zero-indexed rank of the square, 0 for the first rank.
+    pub fn rank(self) -> i32 {
+        self.index() as i32 / 8
+    }
+
+    /// Returns the zero-indexed file of the square, 0 for the a-file.
+    pub fn file(self) -> i32 {
+        self.index() as i32 % 8
+    }
+
+    /// Returns the square the given number of ranks and files away, or None if it is off the board.
+    pub fn offset(self, ranks: i32, files: i32) -> Option<Square> {
+        Self::new(self.rank() + ranks, self.file() + files)
+    }
+
+    /// Returns an iterator over all squares, a1, b1 and so on up to h8.
+    pub fn iter() -> impl Iterator<Item = Square> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns an iterator over the squares of a zero-indexed rank, from the a-file to the h-file.
+    pub fn rank_iter(rank: i32) -> impl Iterator<Item = Square> {
+        (0..8).filter_map(move |file| Self::new(rank, file))
+    }
+
+    /// Returns an iterator over the squares of a zero-indexed file, from the first rank to the eighth.
+    pub fn file_iter(file: i32) -> impl Iterator<Item = Square> {
+        (0..8).filter_map(move |rank| Self::new(rank, file))
+    }
+
+    /// Returns the square of a playable index in the board's 10x12 mailbox.
+    pub(crate) fn from_mailbox(index: usize) -> Square {
+        Self::ALL[square_of(index)]
+    }
+
+    /// Returns the index of the square in the board's 10x12 mailbox.
+    pub(crate) fn mailbox(self) -> usize {
+        index_of(self.index())
+    }
+}
+
+impl std::fmt::Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let file = (b'a' + self.file() as u8) as char;
+        let rank = (b'1' + self.rank() as u8) as char;
+
+        write!(f, "{file}{rank}")
+    }
+}
+
+impl std::str::FromStr for Square {
+    type Err = SquareError;
+
+    /// Parses a square in algebraic notation, in either case (e4, E4 etc.).
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let mut chars = str.chars();
+
+        let square = match (chars.next(), chars.next(), chars.next()) {
+            (Some(file @ ('a'..='h' | 'A'..='H')), Some(rank @ '1'..='8'), None) => {
+                let file = file.to_ascii_lowercase() as i32 - 'a' as i32;
+                let rank = rank as i32 - '1' as i32;
+
+                Self::new(rank, file)
+            },
+            _ => None,
+        };
+
+        square.ok_or_else(|| SquareError::InvalidString(str.to_string()))
+    }
+}
+
+impl From<Square> for Pos {
+    fn from(square: Square) -> Self {
+        Pos { rank: square.rank(), file: square.file() }
+    }
+}
+
+impl TryFrom<Pos> for Square {
+    type Error = SquareError;
+
+    fn try_from(pos: Pos) -> Result<Self, Self::Error> {
+        Square::new(pos.rank, pos.file).ok_or(SquareError::OutOfBounds(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square() {
+        assert_eq!(Square::new(3, 4), Some(Square::E4));
+        assert_eq!(Square::new(8, 0), None);
+        assert_eq!(Square::from_index(63), Some(Square::H8));
+        assert_eq!(Square::G1.rank(), 0);
+        assert_eq!(Square::G1.file(), 6);
+        assert_eq!(Square::E2.offset(2, 0), Some(Square::E4));
+        assert_eq!(Square::H2.offset(0, 1), None);
+
+        assert_eq!(Square::A2.mailbox(), 31);
+        assert_eq!(Square::from_mailbox(98), Square::H8);
+
+        assert_eq!(Square::iter().count(), 64);
+        assert!(Square::iter().enumerate().all(|(index, square)| square.index() == index));
+        assert_eq!(Square::rank_iter(7).last(), Some(Square::H8));
+        assert_eq!(Square::file_iter(2).nth(1), Some(Square::C2));
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Square::E4.to_string(), "e4");
+        assert_eq!("e4".parse(), Ok(Square::E4));
+        assert_eq!("H8".parse(), Ok(Square::H8));
+        assert_eq!("i1".parse::<Square>(), Err(SquareError::InvalidString("i1".to_string())));
+        assert!("e44".parse::<Square>().is_err());
+
+        assert_eq!(Pos::from(Square::C6), Pos { rank: 5, file: 2 });
+        assert_eq!(Square::try_from(Pos { rank: 5, file: 2 }), Ok(Square::C6));
+        assert!(Square::try_from(Pos { rank: -1, file: 2 }).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+//! Zobrist keys, random 64-bit numbers xor-ed together to hash a position.
+//! 
+//! The keys are generated at compile time from a fixed seed, so hashes are stable between runs and builds.
+
+/// Returns the next number of a splitmix64 sequence, and the advanced state.
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    (z ^ (z >> 31), state)
+}
+
+const KEY_COUNT: usize = 2 * 6 * 64 + 1 + 4 + 8;
+
+const fn gen_keys() -> [u64; KEY_COUNT] {
+    let mut keys = [0; KEY_COUNT];
+    let mut state = 0x5EED_C0DE_CAFE_F00D;
+
+    let mut i = 0;
+    while i < KEY_COUNT {
+        let (key, next) = splitmix64(state);
+        keys[i] = key;
+        state = next;
+        i += 1;
+    }
+
+    keys
+}
+
+const KEYS: [u64; KEY_COUNT] = gen_keys();
+
+/// Returns the key of a piece, given by color and piece type as stored in the bitboards, on a square (0 to 63).
+pub(crate) const fn piece_key(color: usize, kind: usize, square: usize) -> u64 {
+    KEYS[(color * 6 + kind) * 64 + square]
+}
+
+/// The key xor-ed in when black is to move.
+pub(crate) const BLACK_TO_MOVE_KEY: u64 = KEYS[2 * 6 * 64];
+
+/// Keys of the castling rights, white king side, white queen side, black king side and black queen side.
+pub(crate) const CASTLING_KEYS: [u64; 4] = [
+    KEYS[2 * 6 * 64 + 1], KEYS[2 * 6 * 64 + 2], KEYS[2 * 6 * 64 + 3], KEYS[2 * 6 * 64 + 4],
+];
+
+/// Returns the key of an en passant target on the given file (0 to 7).
+pub(crate) const fn en_passant_key(file: usize) -> u64 {
+    KEYS[2 * 6 * 64 + 5 + file]
+}
@@ -0,0 +1,154 @@
+use crate::board::*;
+use crate::game::*;
+
+/// Represents any error produced by the library, used to handle them all in one place.
+/// 
+/// Every error type of the library converts into it, so it can be returned with the `?` operator.
+/// The message is that of the original error, which includes the errors it wraps,
+/// so no error in the chain of sources repeats it.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use api::{ChessError, Game};
+/// 
+/// fn play(fen: &str, plys: &[&str]) -> Result<Game, ChessError> {
+///     let mut game = Game::from_fen(fen)?;
+/// 
+///     for ply in plys {
+///         game.ply_san(ply)?;
+///     }
+/// 
+///     Ok(game)
+/// }
+/// 
+/// assert!(play("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", &["e4", "Kd7"]).is_ok());
+/// assert!(matches!(play("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", &["e5"]), Err(ChessError::Ply(_))));
+/// assert!(matches!(play("8/8/8/8/8/8/4P3/4K3 w - - 0 1", &[]), Err(ChessError::Fen(_))));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChessError {
+    Board(BoardError),
+    Square(SquareError),
+    Ply(PlyError),
+    Position(PositionError),
+    Fen(FenParseError),
+    San(SanParseError),
+    Pgn(PgnParseError),
+    Uci(UciParseError),
+}
+
+impl std::fmt::Display for ChessError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChessError::Board(e) => write!(formatter, "{e}"),
+            ChessError::Square(e) => write!(formatter, "{e}"),
+            ChessError::Ply(e) => write!(formatter, "{e}"),
+            ChessError::Position(e) => write!(formatter, "{e}"),
+            ChessError::Fen(e) => write!(formatter, "{e}"),
+            ChessError::San(e) => write!(formatter, "{e}"),
+            ChessError::Pgn(e) => write!(formatter, "{e}"),
+            ChessError::Uci(e) => write!(formatter, "{e}"),
+        }
+    }
+}
+
+// The error is transparent, its message is that of the wrapped error, 
+// so the wrapped error is not returned as a source to avoid repeating the message.
+impl std::error::Error for ChessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ChessError::Board(e) => e.source(),
+            ChessError::Square(e) => e.source(),
+            ChessError::Ply(e) => e.source(),
+            ChessError::Position(e) => e.source(),
+            ChessError::Fen(e) => e.source(),
+            ChessError::San(e) => e.source(),
+            ChessError::Pgn(e) => e.source(),
+            ChessError::Uci(e) => e.source(),
+        }
+    }
+}
+
+impl From<BoardError> for ChessError {
+    fn from(error: BoardError) -> Self {
+        ChessError::Board(error)
+    }
+}
+
+impl From<SquareError> for ChessError {
+    fn from(error: SquareError) -> Self {
+        ChessError::Square(error)
+    }
+}
+
+impl From<PlyError> for ChessError {
+    fn from(error: PlyError) -> Self {
+        ChessError::Ply(error)
+    }
+}
+
+impl From<PositionError> for ChessError {
+    fn from(error: PositionError) -> Self {
+        ChessError::Position(error)
+    }
+}
+
+impl From<FenParseError> for ChessError {
+    fn from(error: FenParseError) -> Self {
+        ChessError::Fen(error)
+    }
+}
+
+impl From<SanParseError> for ChessError {
+    fn from(error: SanParseError) -> Self {
+        ChessError::San(error)
+    }
+}
+
+impl From<PgnParseError> for ChessError {
+    fn from(error: PgnParseError) -> Self {
+        ChessError::Pgn(error)
+    }
+}
+
+impl From<UciParseError> for ChessError {
+    fn from(error: UciParseError) -> Self {
+        ChessError::Uci(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn chess_error() {
+        let error: ChessError = Game::from_uci_position("startpos moves e2e5").unwrap_err().into();
+
+        assert_eq!(error.to_string(), "uci parsing error: ply 1: invalid ply: the piece on e2 cannot move to e5");
+
+        // Error reporters print every message in the chain of sources, each message must appear once.
+        let chain = |error: &dyn Error| {
+            let mut messages = vec![error.to_string()];
+            let mut source = error.source();
+
+            while let Some(error) = source {
+                messages.push(error.to_string());
+                source = error.source();
+            }
+
+            messages.join(": ")
+        };
+
+        assert_eq!(chain(&error), "uci parsing error: ply 1: invalid ply: the piece on e2 cannot move to e5");
+
+        let error: ChessError = crate::default_game().ply_san("Nf4").unwrap_err().into();
+        assert_eq!(chain(&error), "invalid ply: san parsing error: no legal ply matches");
+
+        // Errors can be sent between threads and boxed.
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(error);
+        assert!(boxed.downcast_ref::<ChessError>().is_some());
+    }
+}
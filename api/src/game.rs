@@ -1,4 +1,4 @@
-pub use crate::board::*;
+use crate::board::*;
 
 mod fen;
 pub use fen::*;
@@ -6,6 +6,93 @@ pub use fen::*;
 mod ply_gen;
 pub use ply_gen::*;
 
+mod status;
+pub use status::*;
+
+mod san;
+pub use san::*;
+
+mod pgn;
+pub use pgn::*;
+
+mod uci;
+pub use uci::*;
+
+mod ply_info;
+pub use ply_info::*;
+
+mod builder;
+pub use builder::*;
+
+mod pieces;
+
+#[cfg(feature = "serde")]
+mod serialize;
+
+mod attacks;
+
+mod perft;
+
+mod hash;
+
+
+/// Represents the castling moves each player is still allowed to make.
+/// 
+/// A right is lost when the king or the corresponding rook moves, or when the rook is captured.
+/// Having a right does not mean castling is currently legal, see [`Game::get_plys`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CastlingRights {
+    pub white_king_side: bool,
+    pub white_queen_side: bool,
+    pub black_king_side: bool,
+    pub black_queen_side: bool,
+}
+
+impl CastlingRights {
+    /// Returns castling rights where neither player may castle.
+    pub fn none() -> Self {
+        Self {
+            white_king_side: false,
+            white_queen_side: false,
+            black_king_side: false,
+            black_queen_side: false,
+        }
+    }
+
+    /// Revokes any rights depending on a king or rook standing on the given index.
+    pub(crate) fn revoke(&mut self, index: usize) {
+        match index {
+            25 => {
+                self.white_king_side = false;
+                self.white_queen_side = false;
+            },
+            21 => self.white_queen_side = false,
+            28 => self.white_king_side = false,
+            95 => {
+                self.black_king_side = false;
+                self.black_queen_side = false;
+            },
+            91 => self.black_queen_side = false,
+            98 => self.black_king_side = false,
+            _ => (),
+        }
+    }
+}
+
+/// Represents everything needed to take back a ply:
+/// the ply itself, the tiles it overwrote and the state it replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PlyRecord {
+    pub(crate) ply: Ply,
+    pub(crate) undo: BoardUndo,
+    pub(crate) castling: CastlingRights,
+    pub(crate) en_passant: Option<usize>,
+    pub(crate) ply_clock: u32,
+    pub(crate) move_clock: u32,
+    pub(crate) hash: u64,
+    pub(crate) en_passant_hash: u64,
+}
 
 /// Represents a game of chess.
 /// 
@@ -15,20 +102,39 @@ pub struct Game {
     board: Board,
     player: Color,
     plys: Vec<Ply>,
+    castling: CastlingRights,
     en_passant: Option<usize>,
+    ply_clock: u32,
+    move_clock: u32,
+    hash: u64,
+    // The part of the hash given by the en passant target, which is only known after generating plys.
+    en_passant_hash: u64,
+    claimed_draw: Option<DrawReason>,
+    positions: Vec<u64>,
+    start_fen: String,
+    history: Vec<PlyRecord>,
+    undone: Vec<Ply>,
 }
 
 impl Game {
     /// Constructs a new Game object.
     pub(crate) fn new() -> Self {
-        let mut game = Self {
+        Self {
             board: Board::new(),
             player: Color::White,
             plys: Vec::new(),
+            castling: CastlingRights::none(),
             en_passant: None,
-        };
-
-        game
+            ply_clock: 0,
+            move_clock: 1,
+            hash: 0,
+            en_passant_hash: 0,
+            claimed_draw: None,
+            positions: Vec::new(),
+            start_fen: String::new(),
+            history: Vec::new(),
+            undone: Vec::new(),
+        }
     }
 
     /// Resets the game to the default state: empty board.
@@ -38,7 +144,36 @@ impl Game {
         self.board = new_game.board;
         self.player = new_game.player;
         self.plys = new_game.plys;
+        self.castling = new_game.castling;
         self.en_passant = new_game.en_passant;
+        self.hash = new_game.hash;
+        self.en_passant_hash = new_game.en_passant_hash;
+        self.ply_clock = new_game.ply_clock;
+        self.move_clock = new_game.move_clock;
+        self.claimed_draw = new_game.claimed_draw;
+        self.positions = new_game.positions;
+        self.start_fen = new_game.start_fen;
+        self.history = new_game.history;
+        self.undone = new_game.undone;
+    }
+
+    /// Resets the game and starts it from the board, player, rights and clocks of a validated position.
+    pub(crate) fn start_from(&mut self, position: Game) {
+        self.renew();
+
+        self.board = position.board;
+        self.player = position.player;
+        self.castling = position.castling;
+        self.en_passant = position.en_passant;
+        self.ply_clock = position.ply_clock;
+        self.move_clock = position.move_clock;
+
+        // The en passant part is added when generating plys.
+        self.hash = self.board.hash() ^ self.state_hash();
+        self.gen_plys();
+
+        self.positions.push(self.hash());
+        self.start_fen = self.to_fen();
     }
 
     /// Used each turn to make plys with positions (see [`Pos`]) as arguments.
@@ -47,58 +182,231 @@ impl Game {
     /// and generates a new set of legal plys. If the ply is illegal it will return an error 
     /// that should be handled. If you wish to make plys using something resembling algebraic notation,
     /// consider using [`ply_str`] instead.
+    /// 
+    /// On success, a description of the ply (see [`PlyInfo`]) is returned, 
+    /// with the moved and captured pieces and every tile that changed.
+    /// 
+    /// Pawns reaching the last rank cannot be moved with this method, 
+    /// use [`ply_promotion`] to choose the promotion piece.
     ///
     /// [`ply_str`]: `Game::ply_str`
+    /// [`ply_promotion`]: `Game::ply_promotion`
     /// 
     /// # Examples
     /// 
     /// Basic use:
     /// ```
+    /// use api::Pos;
+    /// 
     /// let mut game = api::default_game();
     /// // "a2 to a4"
-    /// match game.ply(Pos {0, 1}, Pos {0, 3}) {
+    /// match game.ply(Pos { rank: 1, file: 0 }, Pos { rank: 3, file: 0 }) {
     ///     Ok(_) => (),
     ///     Err(_) => (), // Handle error.
     /// }
     /// 
     /// ```
-    pub fn ply(&mut self, origin: Pos, destination: Pos) -> Result<(), PlyError> {
-        let origin_index = Board::get_index(origin);
-        let destination_index = Board::get_index(destination);
-        let ply = Ply {
-            origin: origin_index,
-            destination: destination_index,
-        };
+    pub fn ply(&mut self, origin: Pos, destination: Pos) -> Result<PlyInfo, PlyError> {
+        match (Square::try_from(origin), Square::try_from(destination)) {
+            (Ok(origin), Ok(destination)) => self.make_ply(Ply { origin, destination, promotion: None }),
+            _ => Err(PlyError::InvalidPly),
+        }
+    }
+
+    /// Used to make plys where a pawn is promoted, with positions (see [`Pos`]) as arguments.
+    /// 
+    /// The promotion tile must be a queen, rook, bishop or knight of the current player's color.
+    /// For more details, see [`ply`].
+    ///
+    /// [`ply`]: `Game::ply`
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// use api::{Color, Pos, Tile};
+    /// 
+    /// let mut game = api::fen_game("8/4P3/8/8/8/8/8/k3K3 w - - 0 1").unwrap();
+    /// // "e7 to e8, promoting to a queen"
+    /// match game.ply_promotion(Pos { rank: 6, file: 4 }, Pos { rank: 7, file: 4 }, Tile::Queen(Color::White)) {
+    ///     Ok(_) => (),
+    ///     Err(_) => (), // Handle error.
+    /// }
+    /// ```
+    pub fn ply_promotion(&mut self, origin: Pos, destination: Pos, promotion: Tile) -> Result<PlyInfo, PlyError> {
+        match (Square::try_from(origin), Square::try_from(destination)) {
+            (Ok(origin), Ok(destination)) => self.make_ply(Ply { origin, destination, promotion: Some(promotion) }),
+            _ => Err(PlyError::InvalidPly),
+        }
+    }
 
-        if !(self.plys.contains(&ply)) {
-            println!("Invalid Ply: {}", ply);
-            return Err(PlyError::InvalidPly);
+    /// Makes a ply if it is legal, and generates the plys of the next player.
+    /// 
+    /// Making a new ply discards the plys that could be redone.
+    pub(crate) fn make_ply(&mut self, ply: Ply) -> Result<PlyInfo, PlyError> {
+        if self.status().is_over() {
+            return Err(PlyError::GameOver);
         }
 
-        let tile = match self.board.rem_tile(origin_index) {
-            Ok(t) => t,
-            Err(_) => return Err(PlyError::Unknown),
-        };
+        self.check_ply(&ply)?;
+
+        let info = self.record_ply(ply)?;
+        self.undone.clear();
+
+        Ok(info)
+    }
+
+    /// Executes a legal ply, generates the plys of the next player and records it in the history.
+    fn record_ply(&mut self, ply: Ply) -> Result<PlyInfo, PlyError> {
+        let record = self.execute_ply(ply)?;
+        let info = self.ply_info(&record);
+
+        self.gen_plys();
+
+        self.positions.push(self.hash());
+        self.history.push(record);
+
+        Ok(info)
+    }
+
+    /// Takes back the last ply, restoring captured pieces, castling and en passant rights and the clocks.
+    /// 
+    /// Returns the ply that was taken back, or None if no plys have been made.
+    /// The ply can be made again with [`redo`].
+    /// 
+    /// [`redo`]: `Game::redo`
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// let mut game = api::default_game();
+    /// game.ply_str("e2e4").unwrap();
+    /// 
+    /// assert!(game.undo().is_some());
+    /// assert_eq!(game.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// assert!(game.undo().is_none());
+    /// ```
+    pub fn undo(&mut self) -> Option<Ply> {
+        let record = self.history.pop()?;
+
+        self.unexecute_ply(&record);
+        self.gen_plys();
+
+        // A draw can only have been claimed in the position that was taken back.
+        self.claimed_draw = None;
+        self.positions.pop();
+        self.undone.push(record.ply);
+
+        Some(record.ply)
+    }
 
-        match self.board.set_tile(destination_index, tile) {
-            Ok(_) => (),
-            Err(_) => return Err(PlyError::Unknown),
+    /// Makes the last ply taken back by [`undo`] again.
+    /// 
+    /// Returns the ply that was made, or None if there is nothing to redo.
+    /// Making any other ply discards the plys that could be redone.
+    /// 
+    /// [`undo`]: `Game::undo`
+    pub fn redo(&mut self) -> Option<Ply> {
+        let ply = self.undone.pop()?;
+
+        match self.record_ply(ply) {
+            Ok(_) => Some(ply),
+            Err(_) => {
+                self.undone.push(ply);
+                None
+            },
         }
+    }
+
+    /// Returns the plys made since the starting position, oldest first.
+    /// 
+    /// Plys taken back with [`undo`] are not included.
+    /// 
+    /// [`undo`]: `Game::undo`
+    pub fn history(&self) -> Vec<Ply> {
+        self.history.iter().map(|record| record.ply).collect()
+    }
+
+    /// Moves the pieces of a ply without checking its validity and passes the turn.
+    /// 
+    /// Returns the record needed to take the ply back, see [`unexecute_ply`].
+    /// 
+    /// [`unexecute_ply`]: `Game::unexecute_ply`
+    pub(crate) fn execute_ply(&mut self, ply: Ply) -> Result<PlyRecord, PlyError> {
+        let (origin, destination) = (ply.origin.mailbox(), ply.destination.mailbox());
+
+        let is_capture = self.board.get_tile(destination) != Some(&tile!(.));
+
+        let tile = self.board.get_tiles()[origin];
 
-        self.player = match self.player {
-            Color::White => Color::Black,
-            Color::Black => Color::White,
+        // The hash without the pieces, the player and castling rights, which are added back after the ply.
+        let hash = self.hash ^ self.board.hash() ^ self.state_hash();
+
+        let undo = self.board.make_ply(origin, destination, ply.promotion, self.en_passant)?;
+
+        let record = PlyRecord {
+            ply,
+            undo,
+            castling: self.castling,
+            en_passant: self.en_passant,
+            ply_clock: self.ply_clock,
+            move_clock: self.move_clock,
+            hash: self.hash,
+            en_passant_hash: self.en_passant_hash,
         };
 
-        self.gen_plys();
+        // Only a double push creates an en passant target, on the tile that was passed.
+        self.en_passant = match tile {
+            tile!(P) | tile!(p) if origin.abs_diff(destination) == 20 => {
+                Some((origin + destination) / 2)
+            },
+            _ => None,
+        };
+
+        self.castling.revoke(origin);
+        self.castling.revoke(destination);
+
+        // The ply clock counts plys since the last pawn move or capture.
+        self.ply_clock = match tile {
+            tile!(P) | tile!(p) => 0,
+            _ if is_capture => 0,
+            _ => self.ply_clock + 1,
+        };
+
+        // The move clock counts full moves, and is incremented after black's ply.
+        if self.player == Color::Black {
+            self.move_clock += 1;
+        }
+
+        self.player = self.opponent();
+
+        // The en passant part is replaced when generating plys for the new position.
+        self.hash = hash ^ self.board.hash() ^ self.state_hash();
+
+        Ok(record)
+    }
 
-        return Ok(());
+    /// Reverses [`execute_ply`] using the record it returned, and passes the turn back.
+    /// 
+    /// [`execute_ply`]: `Game::execute_ply`
+    pub(crate) fn unexecute_ply(&mut self, record: &PlyRecord) {
+        self.board.unmake_ply(&record.undo);
+
+        self.castling = record.castling;
+        self.en_passant = record.en_passant;
+        self.hash = record.hash;
+        self.en_passant_hash = record.en_passant_hash;
+        self.ply_clock = record.ply_clock;
+        self.move_clock = record.move_clock;
+
+        self.player = self.opponent();
     }
 
     /// Used each turn to make plys using text.
     /// 
     /// Valid arguments resembles algebraic notation (a1a2 etc.), 
     /// but only positions are allowed meaning piece names cannot be used.
+    /// Promotions are written with the letter of the chosen piece last (e7e8q etc.).
     /// For more details or if wishing to use positions (see[`Pos`]), see [`ply`].
     ///
     /// [`ply`]: `Game::ply`
@@ -124,15 +432,25 @@ impl Game {
     ///     Err(_) => (), // Handle error.
     /// }
     /// ```
-    pub fn ply_str(&mut self, str: &str) -> Result<(), PlyError> {
+    pub fn ply_str(&mut self, str: &str) -> Result<PlyInfo, PlyError> {
+        let promotion = match str.split_whitespace().collect::<String>().to_lowercase().chars().nth(4) {
+            Some('q') => Some(Tile::Queen(self.player)),
+            Some('r') => Some(Tile::Rook(self.player)),
+            Some('b') => Some(Tile::Bishop(self.player)),
+            Some('n') => Some(Tile::Knight(self.player)),
+            Some(_) => return Err(PlyError::InvalidPly),
+            None => None,
+        };
+
         let ply = Self::get_pos_from_str(str);
         match ply {
             Some(x) => {
                 match x.0 {
                     Some(origin) => {
                         match x.1 {
-                            Some(destination) => {
-                                self.ply(origin, destination)
+                            Some(destination) => match promotion {
+                                Some(promotion) => self.ply_promotion(origin, destination, promotion),
+                                None => self.ply(origin, destination),
                             },
                             None => Err(PlyError::InvalidPly),
                         }
@@ -159,15 +477,19 @@ impl Game {
     /// 
     /// [`get_plys_from_str`]: `Game::get_plys_from_str
     pub fn get_plys_from_pos(&self, pos: Pos) -> Vec<&Ply> {
-        let mut plys = Vec::new();
-
-        for ply in &self.plys {
-            if Board::get_pos(ply.origin) == pos {
-                plys.push(ply)
-            }
+        match Square::try_from(pos) {
+            Ok(square) => self.get_plys_from_square(square),
+            Err(_) => Vec::new(),
         }
+    }
 
-        plys
+    /// Returns all valid plys available from the chosen square (see [`Square`]).
+    /// 
+    /// For more details, see [`get_plys_from_pos`].
+    /// 
+    /// [`get_plys_from_pos`]: `Game::get_plys_from_pos
+    pub fn get_plys_from_square(&self, square: Square) -> Vec<&Ply> {
+        self.plys.iter().filter(|ply| ply.origin == square).collect()
     }
 
     /// Returns all valid plys available from the chosen tile position passed as a string slice.
@@ -190,8 +512,26 @@ impl Game {
         &self.player
     }
 
-    pub fn is_checkmate(&self) -> bool {
-        self.plys.is_empty()
+    /// Returns the castling rights (see [`CastlingRights`]) of both players.
+    pub fn get_castling_rights(&self) -> &CastlingRights {
+        &self.castling
+    }
+
+    /// Returns the number of plys since the last pawn move or capture.
+    pub fn get_ply_clock(&self) -> u32 {
+        self.ply_clock
+    }
+
+    /// Returns the number of the current full move, starting at 1 and incremented after black's ply.
+    pub fn get_move_clock(&self) -> u32 {
+        self.move_clock
+    }
+
+    /// Returns the square (see [`Square`]) a pawn can be captured on en passant, if any.
+    /// 
+    /// The target is set after every double push, even if no pawn is able to make the capture.
+    pub fn get_en_passant(&self) -> Option<Square> {
+        self.en_passant.map(Square::from_mailbox)
     }
 
     /// Returns the tile at the specified position (see [`Pos`]).
@@ -200,10 +540,14 @@ impl Game {
     /// 
     /// [`get_tile_from_str`]: `Game::get_tile_from_str`
     pub fn get_tile_from_pos(&self, pos: Pos) -> Option<&Tile> {
-        match self.board.get_tile(Board::get_index(pos))? {
-            tile!(_) => None,
-            x => Some(x),
-        }
+        let square = Square::try_from(pos).ok()?;
+
+        Some(self.get_tile_from_square(square))
+    }
+
+    /// Returns the tile on the specified square (see [`Square`]).
+    pub fn get_tile_from_square(&self, square: Square) -> &Tile {
+        &self.board.get_tiles()[square.mailbox()]
     }
 
     /// Returns the tile at the specified algebraic notation-based position.
@@ -215,16 +559,14 @@ impl Game {
         self.get_tile_from_pos(Self::get_pos_from_str(str)?.0?)
     }
 
-    /// Returns all the tiles on the board as a one-dimensional array.
+    /// Returns all the tiles on the board as a one-dimensional array, ordered from a1 to h8 (see [`Square::index`]).
     pub fn get_board_1d(&self) -> [&Tile; 64] {
         let mut board = [&Tile::Empty; 64];
 
-        let index = 0;
-        for tile in self.board.get_tiles() {
-            match tile {
-                tile!(_) => (),
-                _ => board[index] = tile,
-            }
+        let tiles = self.board.get_tiles().iter().filter(|tile| **tile != tile!(_));
+
+        for (entry, tile) in board.iter_mut().zip(tiles) {
+            *entry = tile;
         }
 
         board
@@ -234,15 +576,15 @@ impl Game {
     pub fn get_board_2d(&self) -> [[&Tile; 8]; 8]{
         let mut board = [[&Tile::Empty; 8]; 8];
 
-        for rank in 0..8 {
-            for file in 0..8 {
+        for (rank, row) in board.iter_mut().enumerate() {
+            for (file, entry) in row.iter_mut().enumerate() {
                 let tile = self.get_tile_from_pos(Pos {
                     rank: rank as i32,
                     file: file as i32,
                 }).unwrap(); // Very bad!
                 match tile {
                     tile!(_) => (),
-                    _ => board[rank][file] = tile,
+                    _ => *entry = tile,
                 }
             }
         }
@@ -280,16 +622,23 @@ impl Game {
             _ => return None,
         };
 
-        let destination_file = match iter.next()? {
-            'a' => 0,
-            'b' => 1,
-            'c' => 2,
-            'd' => 3,
-            'e' => 4,
-            'f' => 5,
-            'g' => 6,
-            'h' => 7,
-            _ => return None,
+        pos_pair.0 = Some(Pos {
+            file: origin_file, 
+            rank: origin_rank,
+        });
+
+        // The destination is optional.
+        let destination_file = match iter.next() {
+            Some('a') => 0,
+            Some('b') => 1,
+            Some('c') => 2,
+            Some('d') => 3,
+            Some('e') => 4,
+            Some('f') => 5,
+            Some('g') => 6,
+            Some('h') => 7,
+            Some(_) => return None,
+            None => return Some(pos_pair),
         };
 
         let destination_rank = match iter.next()? {
@@ -304,11 +653,6 @@ impl Game {
             _ => return None,
         };
 
-        pos_pair.0 = Some(Pos {
-            file: origin_file, 
-            rank: origin_rank,
-        });
-
         pos_pair.1 = Some(Pos {
             file: destination_file, 
             rank: destination_rank,
@@ -380,8 +724,195 @@ mod tests {
 
         println!("{:?}", game);
 
-        game.ply_str("a2 a3");
+        game.ply_str("a2 a3").unwrap();
 
         println!("{:?}", game);
     }
+
+    #[test]
+    fn castling() {
+        let mut game = Game::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1").unwrap();
+
+        game.ply_str("e1g1").unwrap();
+
+        assert_eq!(game.get_tile_from_str("g1"), Some(&tile!(K)));
+        assert_eq!(game.get_tile_from_str("f1"), Some(&tile!(R)));
+        assert_eq!(game.get_tile_from_str("h1"), Some(&tile!(.)));
+        assert!(!game.get_castling_rights().white_king_side);
+        assert!(!game.get_castling_rights().white_queen_side);
+
+        game.ply_str("e8c8").unwrap();
+
+        assert_eq!(game.get_tile_from_str("c8"), Some(&tile!(k)));
+        assert_eq!(game.get_tile_from_str("d8"), Some(&tile!(r)));
+        assert_eq!(game.get_tile_from_str("a8"), Some(&tile!(.)));
+        assert_eq!(*game.get_castling_rights(), CastlingRights::none());
+    }
+
+    #[test]
+    fn castling_rights() {
+        // Rook moves and captures revoke the affected side only.
+        let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        game.ply_str("a1a8").unwrap();
+
+        assert!(!game.get_castling_rights().white_queen_side);
+        assert!(!game.get_castling_rights().black_queen_side);
+        assert!(game.get_castling_rights().white_king_side);
+        assert!(game.get_castling_rights().black_king_side);
+        assert!(game.ply_str("e8c8").is_err());
+    }
+
+    #[test]
+    fn en_passant() {
+        let mut game = Game::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - - 0 1").unwrap();
+
+        game.ply_str("d7d5").unwrap();
+
+        assert_eq!(game.get_en_passant(), Some(Square::D6));
+
+        game.ply_str("e5d6").unwrap();
+
+        assert_eq!(game.get_tile_from_str("d6"), Some(&tile!(P)));
+        assert_eq!(game.get_tile_from_str("d5"), Some(&tile!(.)));
+        assert_eq!(game.get_en_passant(), None);
+    }
+
+    #[test]
+    fn en_passant_discovered_check() {
+        // Capturing would remove both pawns from the rank and expose the king to the rook.
+        let game = Game::from_fen("8/8/8/KPp4r/8/8/8/4k3 w - c6 0 1").unwrap();
+
+        assert_eq!(game.get_en_passant(), Some(Square::C6));
+        assert!(game.get_plys_from_str("b5").iter().all(|ply| ply.destination != Square::C6));
+    }
+
+    #[test]
+    fn promotion() {
+        let mut game = Game::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(game.get_plys_from_str("a7").len(), 8);
+        assert!(matches!(game.ply_str("a7a8"), Err(PlyError::MissingPromotion)));
+        assert!(matches!(game.ply_str("a7b8x"), Err(PlyError::InvalidPly)));
+
+        game.ply_str("a7b8n").unwrap();
+
+        assert_eq!(game.get_tile_from_str("b8"), Some(&tile!(N)));
+        assert_eq!(game.get_tile_from_str("a7"), Some(&tile!(.)));
+    }
+
+    #[test]
+    fn castling_through_check() {
+        // The bishop on a6 covers f1, and the rook on d8 covers d1.
+        let game = Game::from_fen("3rk3/8/b7/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+
+        assert!(game.get_plys_from_str("e1").iter().all(|ply| ply.destination != Square::G1));
+        assert!(game.get_plys_from_str("e1").iter().all(|ply| ply.destination != Square::C1));
+
+        // In check.
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K2r w Q - 0 1").unwrap();
+
+        assert!(game.get_plys_from_str("e1").iter().all(|ply| ply.destination != Square::C1));
+    }
+
+    #[test]
+    fn undo() {
+        // Each ply is taken back to the exact starting position: capture, castling, en passant and promotion.
+        let fens = [
+            ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 5 10", ["e1g1", "e1c1", "a1a8", "h1h8"]),
+            ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 7", ["e5d6", "e5e6", "e1d2", "e1f1"]),
+            ("1n2k3/P7/8/8/8/8/8/4K3 w - - 3 40", ["a7b8q", "a7a8n", "e1d1", "e1e2"]),
+        ];
+
+        for (fen, plys) in fens {
+            let mut game = Game::from_fen(fen).unwrap();
+
+            for ply in plys {
+                game.ply_str(ply).unwrap();
+                assert!(game.undo().is_some());
+                assert_eq!(game.to_fen(), fen);
+                assert_eq!(game.repetition_count(), 1);
+            }
+        }
+
+        let mut game = Game::from_fen("8/8/8/8/8/8/8/k3K3 w - - 0 1").unwrap();
+
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.get_plys().len(), 5);
+    }
+
+    #[test]
+    fn redo() {
+        let mut game = crate::default_game();
+
+        for ply in ["e2e4", "e7e5", "g1f3"] {
+            game.ply_str(ply).unwrap();
+        }
+
+        let fen = game.to_fen();
+        let history = game.history();
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(game.undo(), Some(history[2]));
+        assert_eq!(game.undo(), Some(history[1]));
+        assert_eq!(game.history(), history[..1]);
+
+        assert_eq!(game.redo(), Some(history[1]));
+        assert_eq!(game.redo(), Some(history[2]));
+        assert_eq!(game.redo(), None);
+        assert_eq!(game.to_fen(), fen);
+        assert_eq!(game.history(), history);
+
+        // A new ply discards the plys that could be redone.
+        game.undo();
+        game.ply_str("b1c3").unwrap();
+
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn undo_game_over() {
+        // Fool's mate.
+        let mut game = crate::default_game();
+
+        for ply in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            game.ply_str(ply).unwrap();
+        }
+
+        assert!(game.status().is_over());
+
+        game.undo();
+
+        assert!(!game.status().is_over());
+        assert!(game.ply_str("d8e7").is_ok());
+    }
+
+    #[test]
+    fn board_1d() {
+        let game = crate::default_game();
+        let board = game.get_board_1d();
+
+        assert_eq!(board[Square::A1.index()], &tile!(R));
+        assert_eq!(board[Square::E1.index()], &tile!(K));
+        assert_eq!(board[Square::E2.index()], &tile!(P));
+        assert_eq!(board[Square::E4.index()], &tile!(.));
+        assert_eq!(board[Square::D8.index()], &tile!(q));
+        assert_eq!(board[Square::H8.index()], &tile!(r));
+    }
+
+    #[test]
+    fn ply_errors() {
+        // The bishop on d2 shields the king from the queen on a5.
+        let mut game = Game::from_fen("4r1k1/8/8/q7/8/8/3BP3/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(game.ply_str("a3a4").unwrap_err(), PlyError::NoPiece(Square::A3));
+        assert_eq!(game.ply_str("a5a4").unwrap_err(), PlyError::NotYourPiece(Square::A5));
+        assert_eq!(game.ply_str("e2e5").unwrap_err(), PlyError::IllegalDestination(Square::E2, Square::E5));
+        assert_eq!(game.ply_str("d2e3").unwrap_err(), PlyError::LeavesKingInCheck);
+        assert_eq!(game.ply_str("e2e3q").unwrap_err(), PlyError::InvalidPromotion(tile!(Q)));
+        assert_eq!(game.ply_str("e2").unwrap_err(), PlyError::InvalidPly);
+
+        assert_eq!(PlyError::LeavesKingInCheck.to_string(), "invalid ply: the king would be left in check");
+        assert!(game.ply_str("d2c3").is_ok());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::board::*;
+
+use super::Game;
+
+impl Game {
+    /// Returns true if the current player's king is attacked.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// let mut game = api::default_game();
+    /// 
+    /// for ply in ["e2e4", "f7f6", "d1h5"] {
+    ///     game.ply_str(ply).unwrap();
+    /// }
+    /// 
+    /// assert!(game.is_in_check());
+    /// ```
+    pub fn is_in_check(&self) -> bool {
+        match self.king_index(self.player) {
+            Some(index) => self.is_attacked(index, self.opponent()),
+            None => false,
+        }
+    }
+
+    /// Returns the squares (see [`Square`]) of the pieces giving check to the current player's king.
+    /// 
+    /// The list is empty if the current player is not in check, and holds two squares on a double check.
+    pub fn checkers(&self) -> Vec<Square> {
+        match self.king_index(self.player) {
+            Some(index) => Squares(self.attackers(index, self.opponent())).map(|square| Square::ALL[square]).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the squares (see [`Square`]) of all pieces of the given color attacking a square, ordered from a1 to h8.
+    /// 
+    /// The attacked square may be empty or hold a piece of either color.
+    /// Pieces attack through the tiles they would move through, regardless of whether moving would be legal,
+    /// so a pinned piece still attacks and a king is attacked by the opposing king next to it.
+    /// Pawns attack diagonally forward, but not the tile in front of them.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use api::{Color, Square};
+    /// 
+    /// let game = api::default_game();
+    /// 
+    /// assert_eq!(game.attackers_of(Square::F3, Color::White), vec![Square::G1, Square::E2, Square::G2]);
+    /// assert!(game.attackers_of(Square::E4, Color::White).is_empty());
+    /// ```
+    pub fn attackers_of(&self, square: Square, color: Color) -> Vec<Square> {
+        Squares(self.attackers(square.mailbox(), color)).map(|square| Square::ALL[square]).collect()
+    }
+
+    /// Returns true if any piece of the given color attacks a square.
+    /// 
+    /// For what counts as an attack, see [`attackers_of`].
+    /// 
+    /// [`attackers_of`]: `Game::attackers_of`
+    pub fn is_square_attacked(&self, square: Square, by: Color) -> bool {
+        self.is_attacked(square.mailbox(), by)
+    }
+
+    /// Returns true if a tile is attacked by any piece of the given color.
+    /// 
+    /// The tile itself may be empty or contain a piece of either color.
+    pub(crate) fn is_attacked(&self, index: usize, by: Color) -> bool {
+        self.attackers(index, by) != 0
+    }
+
+    /// Returns the bitboard of all pieces of the given color attacking a tile.
+    pub(crate) fn attackers(&self, index: usize, by: Color) -> Bitboard {
+        let square = square_of(index);
+        let occupied = self.board.occupied();
+
+        let diagonal = self.board.bitboard(Tile::Bishop(by)) | self.board.bitboard(Tile::Queen(by));
+        let straight = self.board.bitboard(Tile::Rook(by)) | self.board.bitboard(Tile::Queen(by));
+
+        // A pawn attacks the tile if a pawn of the other color on the tile would attack it back.
+        let defender = match by {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+
+        PAWN_ATTACKS[defender as usize][square] & self.board.bitboard(Tile::Pawn(by))
+            | KNIGHT_ATTACKS[square] & self.board.bitboard(Tile::Knight(by))
+            | KING_ATTACKS[square] & self.board.bitboard(Tile::King(by))
+            | bishop_attacks(square, occupied) & diagonal
+            | rook_attacks(square, occupied) & straight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let game = crate::default_game();
+
+        assert!(!game.is_in_check());
+        assert!(game.checkers().is_empty());
+
+        // Double check from the knight on f6 and the rook on e1.
+        let game = Game::from_fen("4k3/8/5N2/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+
+        assert!(game.is_in_check());
+        assert_eq!(game.checkers(), vec![Square::E1, Square::F6]);
+    }
+
+    #[test]
+    fn attackers() {
+        let game = Game::from_fen("4k3/8/8/3p4/2P1N3/8/1B6/R3K3 w - - 0 1").unwrap();
+
+        // Only the pawn attacks d5, the pawn on d5 attacks back but not the knight in front of it.
+        assert_eq!(game.attackers_of(Square::D5, Color::White), vec![Square::C4]);
+        assert_eq!(game.attackers_of(Square::C4, Color::Black), vec![Square::D5]);
+        assert_eq!(game.attackers_of(Square::A8, Color::White), vec![Square::A1]);
+        assert_eq!(game.attackers_of(Square::G7, Color::White), vec![Square::B2]);
+        assert_eq!(game.attackers_of(Square::D2, Color::White), vec![Square::E1, Square::E4]);
+
+        assert!(game.is_square_attacked(Square::F2, Color::White));
+        assert!(!game.is_square_attacked(Square::H1, Color::White));
+        assert!(game.is_square_attacked(Square::D7, Color::Black));
+    }
+}
@@ -0,0 +1,356 @@
+use crate::board::*;
+
+use super::{CastlingRights, Game};
+
+/// Represents an illegal setup found when building a position (see [`PositionBuilder::build`]).
+#[derive(Debug, Clone, PartialEq)]
+pub enum PositionError {
+    MissingKing(Color),
+    /// A player has more than one king, the square is that of the second king from a1.
+    TooManyKings(Color, Square),
+    PawnOnBackRank(Square),
+    /// The player who is not to move is in check, meaning their king could be captured.
+    OpponentInCheck,
+    /// A castling right is set while the king or the rook is not on its original square.
+    InvalidCastling { color: Color, king_side: bool },
+    /// The en passant target is not the square passed by a pawn of the opponent's double push.
+    InvalidEnPassant(Square),
+    /// The move clock starts at 1.
+    InvalidMoveClock,
+}
+
+impl std::fmt::Display for PositionError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PositionError::MissingKing(color) => write!(formatter, "invalid position: {color} has no king"),
+            PositionError::TooManyKings(color, square) => write!(formatter, "invalid position: {color} has a second king on {square}"),
+            PositionError::PawnOnBackRank(square) => write!(formatter, "invalid position: pawn on {square}, on the first or last rank"),
+            PositionError::OpponentInCheck => write!(formatter, "invalid position: the player not to move is in check"),
+            PositionError::InvalidCastling { color, king_side } => {
+                let side = match king_side {
+                    true => "king side",
+                    false => "queen side",
+                };
+
+                write!(formatter, "invalid position: {color} cannot castle {side} without the king and rook on their original squares")
+            },
+            PositionError::InvalidEnPassant(square) => write!(formatter, "invalid position: no pawn can be captured en passant on {square}"),
+            PositionError::InvalidMoveClock => write!(formatter, "invalid position: the move clock starts at 1"),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// Used to set up a custom position piece by piece, and then start a game from it.
+/// 
+/// The builder starts from an empty board with white to move, no castling rights and no en passant target.
+/// Setters can be chained, and the position is only checked when calling [`build`].
+/// 
+/// [`build`]: `PositionBuilder::build`
+/// 
+/// # Examples
+/// 
+/// ```
+/// use api::{Color, PositionBuilder, Square, Tile};
+/// 
+/// let game = PositionBuilder::new()
+///     .piece(Square::E1, Tile::King(Color::White))
+///     .piece(Square::E8, Tile::King(Color::Black))
+///     .piece(Square::D2, Tile::Pawn(Color::White))
+///     .player(Color::Black)
+///     .build()
+///     .unwrap();
+/// 
+/// assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/3P4/4K3 b - - 0 1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PositionBuilder {
+    board: Board,
+    player: Color,
+    castling: CastlingRights,
+    en_passant: Option<Square>,
+    ply_clock: u32,
+    move_clock: u32,
+}
+
+impl Default for PositionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PositionBuilder {
+    /// Returns a builder with an empty board, see [`PositionBuilder`].
+    pub fn new() -> Self {
+        Self {
+            board: Board::new(),
+            player: Color::White,
+            castling: CastlingRights::none(),
+            en_passant: None,
+            ply_clock: 0,
+            move_clock: 1,
+        }
+    }
+
+    /// Returns a builder holding the current position of a game, used to edit an existing position.
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            board: game.board.clone(),
+            player: game.player,
+            castling: game.castling,
+            en_passant: game.en_passant.map(Square::from_mailbox),
+            ply_clock: game.ply_clock,
+            move_clock: game.move_clock,
+        }
+    }
+
+    /// Places a piece on a square, replacing any piece already there.
+    /// 
+    /// Placing an empty tile removes the piece, and sentinel tiles are ignored.
+    pub fn piece(&mut self, square: Square, tile: Tile) -> &mut Self {
+        // Only fails for sentinel tiles, which are never placed.
+        let _ = self.board.set_tile(square.mailbox(), tile);
+
+        self
+    }
+
+    /// Removes the piece on a square, if any.
+    pub fn remove(&mut self, square: Square) -> &mut Self {
+        let _ = self.board.rem_tile(square.mailbox());
+
+        self
+    }
+
+    /// Removes every piece from the board.
+    pub fn clear(&mut self) -> &mut Self {
+        self.board.empty();
+
+        self
+    }
+
+    /// Sets the player to move.
+    pub fn player(&mut self, player: Color) -> &mut Self {
+        self.player = player;
+
+        self
+    }
+
+    /// Sets the castling rights (see [`CastlingRights`]) of both players.
+    pub fn castling(&mut self, castling: CastlingRights) -> &mut Self {
+        self.castling = castling;
+
+        self
+    }
+
+    /// Sets the square a pawn can be captured on en passant, the square passed by the opponent's last double push.
+    pub fn en_passant(&mut self, en_passant: Option<Square>) -> &mut Self {
+        self.en_passant = en_passant;
+
+        self
+    }
+
+    /// Sets the number of plys since the last pawn move or capture.
+    pub fn ply_clock(&mut self, ply_clock: u32) -> &mut Self {
+        self.ply_clock = ply_clock;
+
+        self
+    }
+
+    /// Sets the number of the current full move, starting at 1.
+    pub fn move_clock(&mut self, move_clock: u32) -> &mut Self {
+        self.move_clock = move_clock;
+
+        self
+    }
+
+    /// Returns the tile on a square of the position being built.
+    pub fn get_tile(&self, square: Square) -> &Tile {
+        &self.board.get_tiles()[square.mailbox()]
+    }
+
+    /// Returns a game starting from the position, or an error (see [`PositionError`]) if the position is illegal.
+    /// 
+    /// Both players must have exactly one king, no pawns may stand on the first or last rank,
+    /// the player not to move may not be in check, and castling rights and the en passant target
+    /// must match the pieces on the board. The same rules apply to fen strings, see [`Game::from_fen`].
+    pub fn build(&self) -> Result<Game, PositionError> {
+        let mut position = Game::new();
+
+        position.board = self.board.clone();
+        position.player = self.player;
+        position.castling = self.castling;
+        position.en_passant = self.en_passant.map(Square::mailbox);
+        position.ply_clock = self.ply_clock;
+        position.move_clock = self.move_clock;
+
+        position.validate()?;
+
+        if self.move_clock == 0 {
+            return Err(PositionError::InvalidMoveClock);
+        }
+
+        let mut game = Game::new();
+        game.start_from(position);
+
+        Ok(game)
+    }
+}
+
+impl Game {
+    /// Returns an error (see [`PositionError`]) if the position is illegal, see [`PositionBuilder::build`].
+    pub(crate) fn validate(&self) -> Result<(), PositionError> {
+        self.validate_kings()?;
+        self.validate_pawns()?;
+        self.validate_opponent_not_in_check()?;
+        self.validate_castling()?;
+        self.validate_en_passant()
+    }
+
+    /// Checks that both players have exactly one king.
+    pub(crate) fn validate_kings(&self) -> Result<(), PositionError> {
+        for color in [Color::White, Color::Black] {
+            let mut kings = Squares(self.board.bitboard(Tile::King(color)));
+
+            if kings.next().is_none() {
+                return Err(PositionError::MissingKing(color));
+            }
+
+            if let Some(square) = kings.next() {
+                return Err(PositionError::TooManyKings(color, Square::ALL[square]));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that no pawns stand on the first or last rank, where they could never have moved to.
+    pub(crate) fn validate_pawns(&self) -> Result<(), PositionError> {
+        let pawns = self.board.bitboard(tile!(P)) | self.board.bitboard(tile!(p));
+        let back_ranks: Bitboard = 0xFF00_0000_0000_00FF;
+
+        match Squares(pawns & back_ranks).next() {
+            Some(square) => Err(PositionError::PawnOnBackRank(Square::ALL[square])),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the king of the player not to move cannot be captured.
+    pub(crate) fn validate_opponent_not_in_check(&self) -> Result<(), PositionError> {
+        match self.king_index(self.opponent()) {
+            Some(index) if self.is_attacked(index, self.player) => Err(PositionError::OpponentInCheck),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that each castling right has the king and the rook on their original squares.
+    pub(crate) fn validate_castling(&self) -> Result<(), PositionError> {
+        let rights = [
+            (self.castling.white_king_side, Color::White, true, 25, 28),
+            (self.castling.white_queen_side, Color::White, false, 25, 21),
+            (self.castling.black_king_side, Color::Black, true, 95, 98),
+            (self.castling.black_queen_side, Color::Black, false, 95, 91),
+        ];
+
+        for (right, color, king_side, king_index, rook_index) in rights {
+            if right && (self.board.get_tile(king_index) != Some(&Tile::King(color))
+                || self.board.get_tile(rook_index) != Some(&Tile::Rook(color))) {
+                return Err(PositionError::InvalidCastling { color, king_side });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the en passant target, if any, is the empty square passed by a pawn of the opponent's double push.
+    pub(crate) fn validate_en_passant(&self) -> Result<(), PositionError> {
+        let Some(index) = self.en_passant else {
+            return Ok(());
+        };
+
+        let square = Square::from_mailbox(index);
+
+        let (rank, pushed_index, origin_index, pushed_pawn) = match self.player {
+            Color::White => (5, index - 10, index + 10, tile!(p)),
+            Color::Black => (2, index + 10, index - 10, tile!(P)),
+        };
+
+        if square.rank() != rank
+            || self.board.get_tile(pushed_index) != Some(&pushed_pawn)
+            || self.board.get_tile(index) != Some(&tile!(.))
+            || self.board.get_tile(origin_index) != Some(&tile!(.)) {
+            return Err(PositionError::InvalidEnPassant(square));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let mut builder = PositionBuilder::new();
+
+        builder
+            .piece(Square::E1, tile!(K))
+            .piece(Square::H1, tile!(R))
+            .piece(Square::E8, tile!(k))
+            .piece(Square::D4, tile!(p))
+            .piece(Square::E4, tile!(P))
+            .castling(CastlingRights { white_king_side: true, ..CastlingRights::none() })
+            .en_passant(Some(Square::E3))
+            .player(Color::Black)
+            .ply_clock(0)
+            .move_clock(12);
+
+        let game = builder.build().unwrap();
+
+        assert_eq!(game.to_fen(), "4k3/8/8/8/3pP3/8/8/4K2R b K e3 0 12");
+        assert_eq!(game.repetition_count(), 1);
+        assert!(game.get_plys().iter().any(|ply| ply.destination == Square::E3));
+
+        // Editing an existing position.
+        let mut builder = PositionBuilder::from_game(&game);
+        builder.remove(Square::D4).en_passant(None);
+
+        assert_eq!(builder.get_tile(Square::D4), &tile!(.));
+        assert_eq!(builder.build().unwrap().to_fen(), "4k3/8/8/8/4P3/8/8/4K2R b K - 0 12");
+
+        builder.clear();
+        assert_eq!(builder.build().unwrap_err(), PositionError::MissingKing(Color::White));
+    }
+
+    #[test]
+    fn errors() {
+        let kings = || {
+            let mut builder = PositionBuilder::new();
+            builder.piece(Square::E1, tile!(K)).piece(Square::E8, tile!(k));
+            builder
+        };
+
+        assert_eq!(PositionBuilder::new().piece(Square::E1, tile!(K)).build().unwrap_err(), PositionError::MissingKing(Color::Black));
+        assert_eq!(kings().piece(Square::A3, tile!(K)).build().unwrap_err(), PositionError::TooManyKings(Color::White, Square::A3));
+        assert_eq!(kings().piece(Square::H8, tile!(P)).build().unwrap_err(), PositionError::PawnOnBackRank(Square::H8));
+        assert_eq!(kings().piece(Square::E4, tile!(Q)).build().unwrap_err(), PositionError::OpponentInCheck);
+        assert_eq!(kings().piece(Square::E4, tile!(q)).player(Color::Black).build().unwrap_err(), PositionError::OpponentInCheck);
+        assert_eq!(kings().move_clock(0).build().unwrap_err(), PositionError::InvalidMoveClock);
+
+        let castling = CastlingRights { black_queen_side: true, ..CastlingRights::none() };
+        assert_eq!(
+            kings().castling(castling).build().unwrap_err(),
+            PositionError::InvalidCastling { color: Color::Black, king_side: false },
+        );
+
+        assert_eq!(kings().en_passant(Some(Square::E6)).build().unwrap_err(), PositionError::InvalidEnPassant(Square::E6));
+        assert_eq!(
+            kings().piece(Square::D5, tile!(p)).en_passant(Some(Square::D3)).build().unwrap_err(),
+            PositionError::InvalidEnPassant(Square::D3),
+        );
+        assert!(kings().piece(Square::D5, tile!(p)).en_passant(Some(Square::D6)).build().is_ok());
+
+        assert_eq!(PositionError::OpponentInCheck.to_string(), "invalid position: the player not to move is in check");
+    }
+}
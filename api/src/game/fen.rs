@@ -1,35 +1,149 @@
 use crate::board::*;
 
-use super::Game;
+use super::{Game, PositionError};
 
-#[derive(Debug, Clone)]
+/// Represents the six space separated fields of a fen string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FenField {
+    PiecePlacement,
+    ActiveColor,
+    CastlingRights,
+    EnPassant,
+    PlyClock,
+    MoveClock,
+}
+
+impl std::fmt::Display for FenField {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let string = match self {
+            FenField::PiecePlacement => "piece placement",
+            FenField::ActiveColor => "active color",
+            FenField::CastlingRights => "castling rights",
+            FenField::EnPassant => "en passant square",
+            FenField::PlyClock => "ply clock",
+            FenField::MoveClock => "move clock",
+        };
+
+        write!(f, "{string}")
+    }
+}
+
+/// Represents an error found while reading a fen string.
+/// 
+/// The last value of every variant is the character offset in the fen string where the error was found,
+/// see [`FenParseError::offset`]. Errors concerning the position as a whole point to the start of
+/// the field that makes the position illegal.
+#[derive(Debug, Clone, PartialEq)]
 pub enum FenParseError {
-    CouldNotRead,
-    InvalidPiece(char),
-    InvalidColor(char),
-    InvalidCastling(char),
-    InvalidEnPassant(char),
-    InvalidPlyClock(char),
-    InvalidMoveClock(char),
-    RankOverflow,
-    FileOverflow,
-    Unknown,
+    /// A field is missing, the offset is the end of the string.
+    CouldNotRead(FenField, usize),
+    InvalidPiece(char, usize),
+    InvalidColor(char, usize),
+    InvalidCastling(char, usize),
+    InvalidEnPassant(char, usize),
+    InvalidPlyClock(char, usize),
+    InvalidMoveClock(char, usize),
+    /// More than eight ranks.
+    RankOverflow(usize),
+    /// More than eight files in a rank.
+    FileOverflow(usize),
+    /// Less than eight ranks.
+    RankUnderflow(usize),
+    /// Less than eight files in a rank.
+    FileUnderflow(usize),
+    /// More than six fields.
+    TrailingField(usize),
+    MissingKing(Color, usize),
+    TooManyKings(Color, usize),
+    PawnOnBackRank(usize),
+    /// The player who is not to move is in check, meaning their king could be captured.
+    OpponentInCheck(usize),
+    Unknown(usize),
 }
 
-impl std::fmt::Display for FenParseError {
-    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl FenParseError {
+    /// Returns the field (see [`FenField`]) containing the error, 
+    /// or None if the error is not part of any of the six fields.
+    pub fn field(&self) -> Option<FenField> {
+        match self {
+            FenParseError::CouldNotRead(field, _) => Some(*field),
+            FenParseError::InvalidPiece(..)
+            | FenParseError::RankOverflow(_)
+            | FenParseError::FileOverflow(_)
+            | FenParseError::RankUnderflow(_)
+            | FenParseError::FileUnderflow(_)
+            | FenParseError::MissingKing(..)
+            | FenParseError::TooManyKings(..)
+            | FenParseError::PawnOnBackRank(_) => Some(FenField::PiecePlacement),
+            FenParseError::InvalidColor(..) | FenParseError::OpponentInCheck(_) => Some(FenField::ActiveColor),
+            FenParseError::InvalidCastling(..) => Some(FenField::CastlingRights),
+            FenParseError::InvalidEnPassant(..) => Some(FenField::EnPassant),
+            FenParseError::InvalidPlyClock(..) => Some(FenField::PlyClock),
+            FenParseError::InvalidMoveClock(..) => Some(FenField::MoveClock),
+            FenParseError::TrailingField(_) | FenParseError::Unknown(_) => None,
+        }
+    }
 
-        write!(formatter, "fen parsing error")
+    /// Returns the character offset in the fen string where the error was found.
+    pub fn offset(&self) -> usize {
+        match self {
+            FenParseError::CouldNotRead(_, offset)
+            | FenParseError::InvalidPiece(_, offset)
+            | FenParseError::InvalidColor(_, offset)
+            | FenParseError::InvalidCastling(_, offset)
+            | FenParseError::InvalidEnPassant(_, offset)
+            | FenParseError::InvalidPlyClock(_, offset)
+            | FenParseError::InvalidMoveClock(_, offset)
+            | FenParseError::RankOverflow(offset)
+            | FenParseError::FileOverflow(offset)
+            | FenParseError::RankUnderflow(offset)
+            | FenParseError::FileUnderflow(offset)
+            | FenParseError::TrailingField(offset)
+            | FenParseError::MissingKing(_, offset)
+            | FenParseError::TooManyKings(_, offset)
+            | FenParseError::PawnOnBackRank(offset)
+            | FenParseError::OpponentInCheck(offset)
+            | FenParseError::Unknown(offset) => *offset,
+        }
     }
 }
 
-impl From<char> for FenParseError {
-    fn from(error: char) -> Self {
-        FenParseError::InvalidPiece(error)
+impl std::fmt::Display for FenParseError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let description = match self {
+            FenParseError::CouldNotRead(..) => "missing field".to_string(),
+            FenParseError::InvalidPiece(char, _) => format!("invalid piece '{char}'"),
+            FenParseError::InvalidColor(char, _) => format!("invalid color '{char}', expected 'w' or 'b'"),
+            FenParseError::InvalidCastling(char, _) => format!("invalid castling right '{char}'"),
+            FenParseError::InvalidEnPassant(char, _) => format!("invalid en passant square at '{char}'"),
+            FenParseError::InvalidPlyClock(char, _) => format!("invalid ply clock at '{char}'"),
+            FenParseError::InvalidMoveClock(char, _) => format!("invalid move clock at '{char}'"),
+            FenParseError::RankOverflow(_) => "more than 8 ranks".to_string(),
+            FenParseError::FileOverflow(_) => "more than 8 files in rank".to_string(),
+            FenParseError::RankUnderflow(_) => "less than 8 ranks".to_string(),
+            FenParseError::FileUnderflow(_) => "less than 8 files in rank".to_string(),
+            FenParseError::TrailingField(_) => "unexpected field after move clock".to_string(),
+            FenParseError::MissingKing(color, _) => format!("{color} has no king"),
+            FenParseError::TooManyKings(color, _) => format!("{color} has more than one king"),
+            FenParseError::PawnOnBackRank(_) => "pawn on first or last rank".to_string(),
+            FenParseError::OpponentInCheck(_) => "the player not to move is in check".to_string(),
+            FenParseError::Unknown(_) => "unknown error".to_string(),
+        };
+
+        match self.field() {
+            Some(field) => write!(formatter, "fen parsing error: {description} in {field} field at offset {}", self.offset()),
+            None => write!(formatter, "fen parsing error: {description} at offset {}", self.offset()),
+        }
     }
 }
 
+impl std::error::Error for FenParseError {}
+
 impl Game {
+    /// Returns a game in the position described by a fen string.
+    /// 
+    /// The string must have all six fields and describe a legal position, 
+    /// otherwise an error (see [`FenParseError`]) is returned pointing at the problem.
     pub fn from_fen(fen: &str) -> Result<Self, FenParseError>{
         let mut game = Self::new();
 
@@ -39,33 +153,49 @@ impl Game {
         }
     }
 
+    /// Replaces the game with the position described by a fen string, see [`Game::from_fen`].
+    /// 
+    /// The game is left unchanged if an error is returned.
     pub fn read_fen(&mut self, fen: &str) -> Result<(), FenParseError>{
-        let mut iter = fen.split_whitespace();
+        let end = fen.chars().count();
+        let mut iter = Self::split_fen(fen).into_iter();
 
         // 1. Piece placement data.
-        let piece_placement_data = iter.next().ok_or(FenParseError::CouldNotRead)?;
+        let (field_offset, piece_placement_data) = iter.next()
+            .ok_or(FenParseError::CouldNotRead(FenField::PiecePlacement, end))?;
 
         let mut game = Game::new();
 
+        // Offsets of the placed pieces, used to point at illegal pieces.
+        let mut piece_offsets = [0; 120];
+
         let mut pos = Pos { 
             rank: 7,
             file: 0,
         };
 
-        for char in piece_placement_data.chars() {
+        for (offset, char) in piece_placement_data.chars().enumerate() {
+            let offset = field_offset + offset;
+
             if let Some(empty_count) = char.to_digit(10) {
-                if pos.file + empty_count as i32 > 8 {
-                    return Err(FenParseError::FileOverflow);
+                if empty_count == 0 {
+                    return Err(FenParseError::InvalidPiece(char, offset));
+                } else if pos.file + empty_count as i32 > 8 {
+                    return Err(FenParseError::FileOverflow(offset));
                 } else {
                     pos.file += empty_count as i32;
                 }
 
             } else if char == '/' {
+                if pos.file < 8 {
+                    return Err(FenParseError::FileUnderflow(offset));
+                }
+
                 pos.rank -= 1;
                 pos.file = 0;
 
                 if pos.rank < 0 {
-                    return Err(FenParseError::RankOverflow);
+                    return Err(FenParseError::RankOverflow(offset));
                 }
             } else {
                 let tile = match char {
@@ -81,53 +211,388 @@ impl Game {
                     'r' => tile!(r),
                     'q' => tile!(q),
                     'k' => tile!(k),
-                    _ => return Err(FenParseError::InvalidPiece(char)),
+                    _ => return Err(FenParseError::InvalidPiece(char, offset)),
                 };
 
+                if pos.file >= 8 {
+                    return Err(FenParseError::FileOverflow(offset));
+                }
+
                 match game.board.set_tile(Board::get_index(pos), tile) {
                     Ok(_) => (),
-                    Err(_) => return Err(FenParseError::Unknown),
+                    Err(_) => return Err(FenParseError::Unknown(offset)),
                 };
 
+                piece_offsets[Board::get_index(pos)] = offset;
+
                 pos.file += 1;
             }
         }
 
+        let field_end = field_offset + piece_placement_data.chars().count();
+
+        if pos.file < 8 {
+            return Err(FenParseError::FileUnderflow(field_end));
+        }
+
+        if pos.rank > 0 {
+            return Err(FenParseError::RankUnderflow(field_end));
+        }
+
+        match game.validate_kings() {
+            Err(PositionError::MissingKing(color)) => return Err(FenParseError::MissingKing(color, field_offset)),
+            Err(PositionError::TooManyKings(color, square)) => return Err(FenParseError::TooManyKings(color, piece_offsets[square.mailbox()])),
+            _ => (),
+        }
+
+        if let Err(PositionError::PawnOnBackRank(square)) = game.validate_pawns() {
+            return Err(FenParseError::PawnOnBackRank(piece_offsets[square.mailbox()]));
+        }
+
         // 2. Active color.
-        let active_color = iter.next().ok_or(FenParseError::CouldNotRead)?;
-
-        game.player = match active_color.chars().next() {
-            Some('w') => Color::White,
-            Some('b') => Color::Black,
-            x => return match x {
-                Some(x) => Err(FenParseError::InvalidColor(x)),
-                None => Err(FenParseError::InvalidColor(' ')),
-            }
+        let (field_offset, active_color) = iter.next()
+            .ok_or(FenParseError::CouldNotRead(FenField::ActiveColor, end))?;
+
+        game.player = match active_color {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => {
+                // Points at the first character that makes the field invalid.
+                let (offset, char) = match active_color.chars().next() {
+                    Some('w' | 'b') => (1, active_color.chars().nth(1).unwrap_or(' ')),
+                    x => (0, x.unwrap_or(' ')),
+                };
+
+                return Err(FenParseError::InvalidColor(char, field_offset + offset));
+            },
         };
 
+        if game.validate_opponent_not_in_check().is_err() {
+            return Err(FenParseError::OpponentInCheck(field_offset));
+        }
+
         // 3. Castling rights.
-        let castling_rights = iter.next().ok_or(FenParseError::CouldNotRead)?;
+        let (field_offset, castling_rights) = iter.next()
+            .ok_or(FenParseError::CouldNotRead(FenField::CastlingRights, end))?;
+
+        if castling_rights != "-" {
+            for (offset, char) in castling_rights.chars().enumerate() {
+                let offset = field_offset + offset;
+
+                let right = match char {
+                    'K' => &mut game.castling.white_king_side,
+                    'Q' => &mut game.castling.white_queen_side,
+                    'k' => &mut game.castling.black_king_side,
+                    'q' => &mut game.castling.black_queen_side,
+                    _ => return Err(FenParseError::InvalidCastling(char, offset)),
+                };
+
+                if *right {
+                    return Err(FenParseError::InvalidCastling(char, offset));
+                }
+
+                *right = true;
+
+                // Each right requires the king and the rook to stand on their original tiles,
+                // the earlier rights have already been checked.
+                if game.validate_castling().is_err() {
+                    return Err(FenParseError::InvalidCastling(char, offset));
+                }
+            }
+        }
 
         // 4. En passant square.
-        let en_passant_square = iter.next().ok_or(FenParseError::CouldNotRead)?;
+        let (field_offset, en_passant_square) = iter.next()
+            .ok_or(FenParseError::CouldNotRead(FenField::EnPassant, end))?;
+
+        if en_passant_square != "-" {
+            let mut chars = en_passant_square.chars();
+
+            let file = match chars.next() {
+                Some(char @ 'a'..='h') => char as i32 - 'a' as i32,
+                Some(char) => return Err(FenParseError::InvalidEnPassant(char, field_offset)),
+                None => return Err(FenParseError::InvalidEnPassant(' ', field_offset)),
+            };
+
+            // The target is the tile passed by a pawn of the player who just moved.
+            let (rank, rank_char) = match game.player {
+                Color::White => (5, '6'),
+                Color::Black => (2, '3'),
+            };
+
+            match chars.next() {
+                Some(char) if char == rank_char => (),
+                Some(char) => return Err(FenParseError::InvalidEnPassant(char, field_offset + 1)),
+                None => return Err(FenParseError::InvalidEnPassant(' ', field_offset + 1)),
+            }
+
+            if let Some(char) = chars.next() {
+                return Err(FenParseError::InvalidEnPassant(char, field_offset + 2));
+            }
+
+            game.en_passant = Some(Board::get_index(Pos { rank, file }));
+
+            if game.validate_en_passant().is_err() {
+                return Err(FenParseError::InvalidEnPassant(en_passant_square.chars().next().unwrap_or(' '), field_offset));
+            }
+        }
 
         // 5. Ply clock.
-        let ply_clock = iter.next().ok_or(FenParseError::CouldNotRead)?;
+        let (field_offset, ply_clock) = iter.next()
+            .ok_or(FenParseError::CouldNotRead(FenField::PlyClock, end))?;
+
+        game.ply_clock = match Self::parse_clock(ply_clock) {
+            Ok(x) => x,
+            Err((offset, char)) => return Err(FenParseError::InvalidPlyClock(char, field_offset + offset)),
+        };
 
         // 6. Move clock.
-        let move_clock = iter.next().ok_or(FenParseError::CouldNotRead)?;
+        let (field_offset, move_clock) = iter.next()
+            .ok_or(FenParseError::CouldNotRead(FenField::MoveClock, end))?;
 
-        self.renew();
+        game.move_clock = match Self::parse_clock(move_clock) {
+            Ok(0) => return Err(FenParseError::InvalidMoveClock('0', field_offset)),
+            Ok(x) => x,
+            Err((offset, char)) => return Err(FenParseError::InvalidMoveClock(char, field_offset + offset)),
+        };
 
-        self.board = game.board;
-        self.player = game.player;
+        if let Some((offset, _)) = iter.next() {
+            return Err(FenParseError::TrailingField(offset));
+        }
 
-        self.gen_plys();
+        self.start_from(game);
 
         Ok(())
     }
 
-    fn write_fen(&self) -> String {
-        todo!();
+    /// Splits a fen string into its fields, paired with the character offset where each field starts.
+    fn split_fen(fen: &str) -> Vec<(usize, &str)> {
+        let mut fields = Vec::new();
+        let mut start = None;
+
+        for (offset, (byte, char)) in fen.char_indices().enumerate() {
+            match (start, char.is_whitespace()) {
+                (None, false) => start = Some((offset, byte)),
+                (Some((field_offset, field_byte)), true) => {
+                    fields.push((field_offset, &fen[field_byte..byte]));
+                    start = None;
+                },
+                _ => (),
+            }
+        }
+
+        if let Some((field_offset, field_byte)) = start {
+            fields.push((field_offset, &fen[field_byte..]));
+        }
+
+        fields
+    }
+
+    /// Returns the value of a clock field, or the offset and value of the first invalid character.
+    /// 
+    /// Only ascii digits are allowed, so signs are rejected.
+    /// If all characters are digits the number is too large, and the first digit is returned.
+    fn parse_clock(clock: &str) -> Result<u32, (usize, char)> {
+        if let Some(invalid) = clock.chars().enumerate().find(|(_, char)| !char.is_ascii_digit()) {
+            return Err(invalid);
+        }
+
+        clock.parse().map_err(|_| (0, clock.chars().next().unwrap_or(' ')))
+    }
+
+    /// Returns the position as a fen string, including castling rights, 
+    /// the en passant square and both clocks.
+    /// 
+    /// Reading the string with [`from_fen`] gives back the same position.
+    /// 
+    /// [`from_fen`]: `Game::from_fen`
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// let mut game = api::default_game();
+    /// game.ply_str("e2e4").unwrap();
+    /// 
+    /// assert_eq!(game.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+    /// ```
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        // 1. Piece placement data.
+        for rank in (0..8).rev() {
+            let mut empty_count = 0;
+
+            for file in 0..8 {
+                match self.board.get_tile(Board::get_index(Pos { rank, file })) {
+                    Some(tile!(.)) => empty_count += 1,
+                    Some(tile) => {
+                        if empty_count > 0 {
+                            fen.push_str(empty_count.to_string().as_str());
+                            empty_count = 0;
+                        }
+                        fen.push_str(tile.to_string().as_str());
+                    },
+                    None => (),
+                }
+            }
+
+            if empty_count > 0 {
+                fen.push_str(empty_count.to_string().as_str());
+            }
+
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        // 2. Active color.
+        fen.push_str(match self.player {
+            Color::White => " w ",
+            Color::Black => " b ",
+        });
+
+        // 3. Castling rights.
+        let castling = [
+            (self.castling.white_king_side, 'K'),
+            (self.castling.white_queen_side, 'Q'),
+            (self.castling.black_king_side, 'k'),
+            (self.castling.black_queen_side, 'q'),
+        ];
+
+        if castling.iter().any(|(right, _)| *right) {
+            fen.extend(castling.iter().filter(|(right, _)| *right).map(|(_, char)| char));
+        } else {
+            fen.push('-');
+        }
+
+        // 4. En passant square.
+        fen.push(' ');
+        match self.en_passant {
+            Some(index) => fen.push_str(Board::index_to_string(index).to_lowercase().as_str()),
+            None => fen.push('-'),
+        }
+
+        // 5. Ply clock and 6. Move clock.
+        fen.push_str(format!(" {} {}", self.ply_clock, self.move_clock).as_str());
+
+        fen
+    }
+}
+
+impl std::fmt::Display for Game {
+    /// Formats the game as a fen string, see [`Game::to_fen`].
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_fen())
+    }
+}
+
+impl std::str::FromStr for Game {
+    type Err = FenParseError;
+
+    /// Parses a fen string, see [`Game::from_fen`].
+    fn from_str(fen: &str) -> Result<Self, Self::Err> {
+        Self::from_fen(fen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_position() {
+        let game = crate::default_game();
+
+        assert_eq!(*game.get_player(), Color::White);
+        assert_eq!(game.get_plys().len(), 20);
+        assert_eq!(game.to_fen(), crate::DEFAULT_FEN);
+    }
+
+    #[test]
+    fn round_trip() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w Kq f6 0 3",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 47 103",
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        ];
+
+        for fen in fens {
+            let game: Game = fen.parse().unwrap();
+
+            assert_eq!(game.to_fen(), fen);
+            assert_eq!(game.to_string(), fen);
+        }
+    }
+
+    #[test]
+    fn round_trip_after_plys() {
+        let mut game = crate::default_game();
+
+        for ply in ["e2e4", "c7c5", "g1f3", "d7d6", "f1b5", "c8d7", "e1g1"] {
+            game.ply_str(ply).unwrap();
+
+            let copy = Game::from_fen(&game.to_fen()).unwrap();
+
+            assert_eq!(copy.to_fen(), game.to_fen());
+            assert_eq!(copy.get_plys(), game.get_plys());
+            assert_eq!(copy.status(), game.status());
+        }
+
+        assert_eq!(game.to_fen(), "rn1qkbnr/pp1bpppp/3p4/1Bp5/4P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 3 4");
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let errors = [
+            ("", FenParseError::CouldNotRead(FenField::PiecePlacement, 0)),
+            ("4k3/8/8/8/8/8/8/4K3 w - -", FenParseError::CouldNotRead(FenField::PlyClock, 25)),
+            ("4k3/8/8/8/8/8/8/4K3 w - - 0 1 x", FenParseError::TrailingField(30)),
+            ("4k3/8/8/8/8/8/8/4K2 w - - 0 1", FenParseError::FileUnderflow(19)),
+            ("4k3/8/8/8/8/8/8/4K4 w - - 0 1", FenParseError::FileOverflow(18)),
+            ("4k3/8/8/8/8/8/4K3 w - - 0 1", FenParseError::RankUnderflow(17)),
+            ("4k3/8/8/8/8/8/8/8/4K3 w - - 0 1", FenParseError::RankOverflow(17)),
+            ("4k3/8/8/7/8/8/8/4K3 w - - 0 1", FenParseError::FileUnderflow(9)),
+            ("4k3/8/8/8/8/8/8/4K2X w - - 0 1", FenParseError::InvalidPiece('X', 19)),
+            ("4k3/8/8/8/8/8/8/4K3 wb - - 0 1", FenParseError::InvalidColor('b', 21)),
+            ("4k3/8/8/8/8/8/8/4K3 w X - 0 1", FenParseError::InvalidCastling('X', 22)),
+            ("4k3/8/8/8/8/8/8/4K3 w - e9 0 1", FenParseError::InvalidEnPassant('9', 25)),
+            ("4k3/8/8/8/8/8/8/4K3 w - - x 1", FenParseError::InvalidPlyClock('x', 26)),
+            ("4k3/8/8/8/8/8/8/4K3 w - - 0 1a", FenParseError::InvalidMoveClock('a', 29)),
+            ("4k3/8/8/8/8/8/8/4K3 w - - 0 0", FenParseError::InvalidMoveClock('0', 28)),
+            ("4k3/8/8/8/8/8/8/4K3 w - - +1 1", FenParseError::InvalidPlyClock('+', 26)),
+            ("4k3/8/8/8/8/8/8/4K3 w - - 0 +1", FenParseError::InvalidMoveClock('+', 28)),
+        ];
+
+        for (fen, error) in errors {
+            assert_eq!(Game::from_fen(fen).unwrap_err(), error, "{fen}");
+        }
+    }
+
+    #[test]
+    fn semantic_errors() {
+        let errors = [
+            ("8/8/8/8/8/8/8/4K3 w - - 0 1", FenParseError::MissingKing(Color::Black, 0)),
+            ("4k3/8/8/8/8/8/8/3KK3 w - - 0 1", FenParseError::TooManyKings(Color::White, 18)),
+            ("4k3/8/8/8/8/8/8/P3K3 w - - 0 1", FenParseError::PawnOnBackRank(16)),
+            ("4k2R/8/8/8/8/8/8/4K3 w - - 0 1", FenParseError::OpponentInCheck(21)),
+            ("4k3/8/8/8/8/8/8/4K3 w K - 0 1", FenParseError::InvalidCastling('K', 22)),
+            ("4k3/8/8/8/8/8/8/4K2R w KK - 0 1", FenParseError::InvalidCastling('K', 24)),
+            ("4k3/8/8/8/8/8/8/4K3 w - e6 0 1", FenParseError::InvalidEnPassant('e', 24)),
+            ("4k3/8/8/4p3/8/8/8/4K3 w - e3 0 1", FenParseError::InvalidEnPassant('3', 27)),
+        ];
+
+        for (fen, error) in errors {
+            assert_eq!(Game::from_fen(fen).unwrap_err(), error, "{fen}");
+        }
+    }
+
+    #[test]
+    fn error_display() {
+        let error = Game::from_fen("4k3/8/8/8/8/8/8/4K2X w - - 0 1").unwrap_err();
+
+        assert_eq!(error.to_string(), "fen parsing error: invalid piece 'X' in piece placement field at offset 19");
     }
 }
\ No newline at end of file
@@ -0,0 +1,175 @@
+use crate::board::*;
+
+use super::Game;
+
+impl Game {
+    /// Returns the 64-bit Zobrist hash of the current position.
+    /// 
+    /// The hash covers the pieces, the player to move, castling rights 
+    /// and the file of the en passant target, if an en passant capture is possible.
+    /// Positions that are the same when testing for repetition have the same hash, 
+    /// and the hash is stable between runs and builds of the library.
+    /// 
+    /// The hash is updated with every ply instead of being computed from the whole position.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// let mut game = api::default_game();
+    /// let start = game.hash();
+    /// 
+    /// for ply in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+    ///     game.ply_str(ply).unwrap();
+    /// }
+    /// 
+    /// assert_eq!(game.hash(), start);
+    /// ```
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the part of the hash given by the player to move and castling rights.
+    pub(crate) fn state_hash(&self) -> u64 {
+        let mut hash = 0;
+
+        if self.player == Color::Black {
+            hash ^= BLACK_TO_MOVE_KEY;
+        }
+
+        let rights = [
+            self.castling.white_king_side,
+            self.castling.white_queen_side,
+            self.castling.black_king_side,
+            self.castling.black_queen_side,
+        ];
+
+        for (right, key) in rights.into_iter().zip(CASTLING_KEYS) {
+            if right {
+                hash ^= key;
+            }
+        }
+
+        hash
+    }
+
+    /// Computes the hash from the whole position, used to check the updated hash.
+    #[cfg(test)]
+    fn compute_hash(&self) -> u64 {
+        let mut hash = self.state_hash();
+
+        for square in Square::iter() {
+            if let Some(piece) = self.board.get_tiles()[square.mailbox()].piece() {
+                hash ^= piece_key(piece.color as usize, piece.kind as usize, square.index());
+            }
+        }
+
+        if let Some(target) = self.en_passant_capture() {
+            hash ^= en_passant_key(Board::get_pos(target).file as usize);
+        }
+
+        hash
+    }
+}
+
+/// Games are equal if they are in the same position, in the sense used when testing for repetition.
+/// 
+/// Clocks and the plys leading to the position are not compared.
+impl PartialEq for Game {
+    fn eq(&self, other: &Self) -> bool {
+        self.position() == other.position()
+    }
+}
+
+impl Eq for Game {}
+
+impl std::hash::Hash for Game {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(Game::hash(self));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash() {
+        let mut game = crate::default_game();
+
+        // The incrementally updated hash matches one computed from scratch.
+        for ply in ["e2e4", "d7d5", "e4d5", "g8f6", "f1b5", "c7c6", "d5c6", "d8d2", "b1d2", "e7e5", "c6b7", "e8e7", "b7a8q"] {
+            game.ply_str(ply).unwrap();
+
+            assert_eq!(game.hash(), Game::from_fen(&game.to_fen()).unwrap().hash());
+        }
+
+        // Taking plys back restores the hash.
+        let start = crate::default_game();
+        while game.undo().is_some() {}
+
+        assert_eq!(game.hash(), start.hash());
+        assert!(game == start);
+    }
+
+    #[test]
+    fn hash_random() {
+        let mut game = Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+
+        // A linear congruential generator, so the sequence is the same every run.
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize
+        };
+
+        for _ in 0..2000 {
+            let plys = game.get_plys().clone();
+
+            // Takes back a ply now and then, and always when the game is over.
+            if plys.is_empty() || game.status().is_over() || next() % 4 == 0 {
+                game.undo();
+            } else if next() % 8 == 0 {
+                game.redo();
+            } else {
+                game.make_ply(plys[next() % plys.len()]).unwrap();
+            }
+
+            assert_eq!(game.hash(), game.compute_hash());
+        }
+    }
+
+    #[test]
+    fn hash_state() {
+        let hash = |fen: &str| Game::from_fen(fen).unwrap().hash();
+
+        let base = hash("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+        // Clocks are not part of the position.
+        assert_eq!(base, hash("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 12 30"));
+
+        assert_ne!(base, hash("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1"));
+        assert_ne!(base, hash("r3k2r/8/8/8/8/8/8/R3K2R w Kkq - 0 1"));
+        assert_ne!(base, hash("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1"));
+
+        // An en passant target only counts if the capture is possible.
+        assert_eq!(hash("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1"), hash("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1"));
+        assert_ne!(hash("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1"), hash("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1"));
+    }
+
+    #[test]
+    fn hash_trait() {
+        use std::collections::HashSet;
+
+        let mut games = HashSet::new();
+        let mut game = crate::default_game();
+
+        games.insert(game.clone());
+
+        for ply in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            game.ply_str(ply).unwrap();
+            games.insert(game.clone());
+        }
+
+        assert_eq!(games.len(), 4);
+    }
+}
@@ -0,0 +1,124 @@
+use super::{Game, Ply, PlyError};
+
+impl Game {
+    /// Counts the leaf nodes of the game tree to the given depth, in plys.
+    /// 
+    /// Perft is used to verify ply generation against published node counts,
+    /// the rules ending a game by draw are not applied, only checkmate and stalemate end a line.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns the error of the first generated ply that could not be made, 
+    /// which means the generator produced an invalid ply.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// let game = api::default_game();
+    /// 
+    /// assert_eq!(game.perft(1), Ok(20));
+    /// assert_eq!(game.perft(2), Ok(400));
+    /// ```
+    pub fn perft(&self, depth: u32) -> Result<u64, PlyError> {
+        let mut game = self.clone();
+
+        game.count_nodes(depth)
+    }
+
+    /// Returns every legal ply together with the number of leaf nodes below it, see [`perft`].
+    /// 
+    /// The counts add up to `perft(depth)`, which helps locating the ply where a generator goes wrong.
+    /// A depth of zero returns no plys.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns the error of the first generated ply that could not be made, like [`perft`].
+    /// 
+    /// [`perft`]: `Game::perft`
+    pub fn divide(&self, depth: u32) -> Result<Vec<(Ply, u64)>, PlyError> {
+        if depth == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut game = self.clone();
+
+        self.plys.iter().map(|ply| {
+            let record = game.execute_ply(*ply)?;
+            game.gen_plys();
+
+            let nodes = game.count_nodes(depth - 1);
+
+            game.unexecute_ply(&record);
+            Ok((*ply, nodes?))
+        }).collect()
+    }
+
+    /// Counts leaf nodes by making and unmaking plys in place, leaving the game as it was, even on errors.
+    fn count_nodes(&mut self, depth: u32) -> Result<u64, PlyError> {
+        match depth {
+            0 => return Ok(1),
+            1 => return Ok(self.plys.len() as u64),
+            _ => (),
+        }
+
+        let plys = std::mem::take(&mut self.plys);
+
+        let nodes = plys.iter().try_fold(0, |nodes, ply| {
+            let record = self.execute_ply(*ply)?;
+            self.gen_plys();
+
+            let below = self.count_nodes(depth - 1);
+
+            self.unexecute_ply(&record);
+            Ok(nodes + below?)
+        });
+
+        self.plys = plys;
+
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Square;
+
+    /// Positions and node counts from the Chess Programming Wiki "Perft Results" page.
+    const POSITIONS: [(&str, &[u64]); 6] = [
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &[20, 400, 8902, 197281]),
+        ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", &[48, 2039, 97862]),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2812, 43238, 674624]),
+        ("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", &[6, 264, 9467, 422333]),
+        ("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", &[44, 1486, 62379]),
+        ("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10", &[46, 2079, 89890]),
+    ];
+
+    #[test]
+    fn perft() {
+        for (fen, counts) in POSITIONS {
+            let game = Game::from_fen(fen).unwrap();
+
+            for (depth, count) in counts.iter().enumerate() {
+                assert_eq!(game.perft(depth as u32 + 1), Ok(*count), "{fen} at depth {}", depth + 1);
+            }
+
+            // Perft leaves the game untouched.
+            assert_eq!(game.to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn divide() {
+        let game = Game::from_fen(POSITIONS[1].0).unwrap();
+        let divide = game.divide(2).unwrap();
+
+        assert_eq!(divide.len(), 48);
+        assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 2039);
+        assert_eq!(game.divide(0), Ok(Vec::new()));
+
+        // Castling king side in Kiwipete leaves black with 43 replies.
+        let castling = divide.iter().find(|(ply, _)| ply.origin == Square::E1 && ply.destination == Square::G1).unwrap();
+        assert_eq!(castling.1, 43);
+    }
+}
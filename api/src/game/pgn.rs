@@ -0,0 +1,702 @@
+use crate::board::*;
+
+use super::{FenParseError, Game, GameStatus, PlyError};
+
+/// Represents the kinds of errors found while reading pgn, see [`PgnParseError`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PgnErrorKind {
+    UnexpectedCharacter(char),
+    UnterminatedTag,
+    UnterminatedComment,
+    /// A variation closed without being opened, opened before any ply, or never closed.
+    UnbalancedVariation,
+    /// A numeric annotation glyph without a ply to annotate, or without a number.
+    InvalidNag,
+    InvalidFen(FenParseError),
+    /// A ply in the movetext, written in standard algebraic notation, that cannot be made.
+    InvalidPly(String, PlyError),
+}
+
+/// Represents an error found while reading pgn, with the line and column (both starting at 1) 
+/// where it was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: PgnErrorKind,
+}
+
+impl std::fmt::Display for PgnParseError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "pgn parsing error at line {}, column {}: ", self.line, self.column)?;
+
+        match &self.kind {
+            PgnErrorKind::UnexpectedCharacter(char) => write!(formatter, "unexpected character '{char}'"),
+            PgnErrorKind::UnterminatedTag => write!(formatter, "unterminated tag"),
+            PgnErrorKind::UnterminatedComment => write!(formatter, "unterminated comment"),
+            PgnErrorKind::UnbalancedVariation => write!(formatter, "unbalanced variation"),
+            PgnErrorKind::InvalidNag => write!(formatter, "invalid numeric annotation glyph"),
+            PgnErrorKind::InvalidFen(e) => write!(formatter, "{e}"),
+            PgnErrorKind::InvalidPly(san, e) => write!(formatter, "{san}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PgnParseError {}
+
+/// Represents a ply in the movetext of a pgn game, together with its annotations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnPly {
+    /// The ply in standard algebraic notation, without annotation glyphs such as "!?".
+    pub san: String,
+    /// Numeric annotation glyphs, annotation glyphs written after the ply are included.
+    pub nags: Vec<u8>,
+    /// Comments written before the ply, only used for the first ply of the game or of a variation.
+    pub comments_before: Vec<String>,
+    /// Comments written after the ply.
+    pub comments: Vec<String>,
+    /// Alternatives to this ply, each a line of plys starting from the position before this ply.
+    pub variations: Vec<Vec<PgnPly>>,
+    line: usize,
+    column: usize,
+}
+
+/// Represents a single game read from pgn: its tags, movetext and result.
+/// 
+/// # Examples
+/// 
+/// Basic use:
+/// ```
+/// let pgn = r#"
+/// [Event "Casual game"]
+/// [Result "0-1"]
+/// 
+/// 1. f3 e5 2. g4 {A blunder.} Qh4# 0-1
+/// "#;
+/// 
+/// let games = api::Pgn::parse(pgn).unwrap();
+/// 
+/// assert_eq!(games[0].tag("Event"), Some("Casual game"));
+/// assert!(games[0].to_game().unwrap().is_checkmate());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pgn {
+    /// Tag pairs in the order they were written, including the seven tag roster.
+    pub tags: Vec<(String, String)>,
+    pub plys: Vec<PgnPly>,
+    /// The game termination marker ("1-0", "0-1", "1/2-1/2" or "*"), if written.
+    pub result: Option<String>,
+    tag_positions: Vec<(usize, usize)>,
+}
+
+impl Pgn {
+    /// Reads every game in a pgn string, such as the contents of a pgn database.
+    /// 
+    /// The movetext is only read, use [`to_game`] to replay and verify the plys.
+    /// 
+    /// [`to_game`]: `Pgn::to_game`
+    pub fn parse(pgn: &str) -> Result<Vec<Pgn>, PgnParseError> {
+        PgnReader::new(pgn).read_games()
+    }
+
+    /// Returns the value of the first tag with the given name.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Replays the main line of the game, and returns the game (see [`Game`]) after the last ply.
+    /// 
+    /// The game starts from the position in the FEN tag if one exists, otherwise from the beginning position.
+    /// The plys of every variation are verified as well.
+    pub fn to_game(&self) -> Result<Game, PgnParseError> {
+        let fen_tag = self.tags.iter().position(|(tag, _)| tag == "FEN");
+        let set_up = self.tag("SetUp") != Some("0");
+
+        let game = match fen_tag {
+            Some(index) if set_up => {
+                let (line, column) = self.tag_positions[index];
+                Game::from_fen(&self.tags[index].1).map_err(|e| PgnParseError {
+                    line,
+                    column,
+                    kind: PgnErrorKind::InvalidFen(e),
+                })?
+            },
+            _ => crate::default_game(),
+        };
+
+        Self::replay(&game, &self.plys)
+    }
+
+    fn replay(game: &Game, plys: &[PgnPly]) -> Result<Game, PgnParseError> {
+        let mut game = game.clone();
+
+        for ply in plys {
+            for variation in &ply.variations {
+                Self::replay(&game, variation)?;
+            }
+
+            game.ply_san(&ply.san).map_err(|e| PgnParseError {
+                line: ply.line,
+                column: ply.column,
+                kind: PgnErrorKind::InvalidPly(ply.san.clone(), e),
+            })?;
+        }
+
+        Ok(game)
+    }
+}
+
+impl Game {
+    /// Returns the game as pgn, with unknown values in the seven tag roster, see [`to_pgn_with_tags`].
+    /// 
+    /// [`to_pgn_with_tags`]: `Game::to_pgn_with_tags`
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// let mut game = api::default_game();
+    /// game.ply_san("e4").unwrap();
+    /// 
+    /// assert!(game.to_pgn().ends_with("\n1. e4 *\n"));
+    /// ```
+    pub fn to_pgn(&self) -> String {
+        self.to_pgn_with_tags(&[])
+    }
+
+    /// Returns the game as pgn, with every ply made since the game was created.
+    /// 
+    /// The seven tag roster is always written, using the given tags where present and "?" otherwise. 
+    /// Other given tags are written after the roster. The result is taken from the game's status
+    /// (see [`Game::status`]) unless a Result tag is given. If the game did not start from the beginning position,
+    /// SetUp and FEN tags are written. The movetext is wrapped to lines of at most 80 characters.
+    pub fn to_pgn_with_tags(&self, tags: &[(&str, &str)]) -> String {
+        let result = match self.status() {
+            GameStatus::Ongoing => "*",
+            GameStatus::Checkmate { winner: Color::White } => "1-0",
+            GameStatus::Checkmate { winner: Color::Black } => "0-1",
+            GameStatus::Stalemate | GameStatus::Draw(_) => "1/2-1/2",
+        };
+
+        let mut roster = [
+            ("Event", "?"),
+            ("Site", "?"),
+            ("Date", "????.??.??"),
+            ("Round", "?"),
+            ("White", "?"),
+            ("Black", "?"),
+            ("Result", result),
+        ];
+
+        for (name, value) in roster.iter_mut() {
+            if let Some((_, tag)) = tags.iter().find(|(tag, _)| tag == name) {
+                *value = tag;
+            }
+        }
+
+        let result = roster[6].1;
+
+        let mut pgn = String::new();
+
+        let mut write_tag = |name: &str, value: &str| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            pgn.push_str(format!("[{name} \"{value}\"]\n").as_str());
+        };
+
+        for (name, value) in roster {
+            write_tag(name, value);
+        }
+
+        if self.start_fen != crate::DEFAULT_FEN {
+            write_tag("SetUp", "1");
+            write_tag("FEN", &self.start_fen);
+        }
+
+        for (name, value) in tags {
+            if !roster.iter().any(|(tag, _)| tag == name) && *name != "SetUp" && *name != "FEN" {
+                write_tag(name, value);
+            }
+        }
+
+        pgn.push('\n');
+
+        // Movetext, replayed from the start to write each ply in standard algebraic notation.
+        let mut tokens = Vec::new();
+        let mut game = match Game::from_fen(&self.start_fen) {
+            Ok(game) => game,
+            Err(_) => return pgn,
+        };
+
+        for (index, ply) in self.history().iter().enumerate() {
+            match game.player {
+                Color::White => tokens.push(format!("{}.", game.move_clock)),
+                Color::Black if index == 0 => tokens.push(format!("{}...", game.move_clock)),
+                Color::Black => (),
+            }
+
+            tokens.push(game.to_san(ply));
+
+            if game.make_ply(*ply).is_err() {
+                break;
+            }
+        }
+
+        tokens.push(result.to_string());
+
+        let mut line = String::new();
+
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() > 80 {
+                pgn.push_str(line.as_str());
+                pgn.push('\n');
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(token.as_str());
+        }
+
+        pgn.push_str(line.as_str());
+        pgn.push('\n');
+
+        pgn
+    }
+
+    /// Returns the game after replaying the first game in a pgn string, see [`Pgn::to_game`].
+    pub fn from_pgn(pgn: &str) -> Result<Self, PgnParseError> {
+        match Pgn::parse(pgn)?.first() {
+            Some(pgn) => pgn.to_game(),
+            None => Ok(crate::default_game()),
+        }
+    }
+}
+
+/// Reads pgn one character at a time, keeping track of the line and column.
+struct PgnReader {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+}
+
+impl PgnReader {
+    fn new(pgn: &str) -> Self {
+        Self {
+            chars: pgn.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+
+        self.index += 1;
+        if char == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(char)
+    }
+
+    fn error(&self, kind: PgnErrorKind) -> PgnParseError {
+        PgnParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    fn error_at(line: usize, column: usize, kind: PgnErrorKind) -> PgnParseError {
+        PgnParseError { line, column, kind }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|char| char.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    /// Reads characters while the condition holds.
+    fn read_while(&mut self, condition: impl Fn(char) -> bool) -> String {
+        let mut string = String::new();
+
+        while let Some(char) = self.peek().filter(|char| condition(*char)) {
+            string.push(char);
+            self.next();
+        }
+
+        string
+    }
+
+    fn read_games(&mut self) -> Result<Vec<Pgn>, PgnParseError> {
+        let mut games = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            if self.peek().is_none() {
+                return Ok(games);
+            }
+
+            games.push(self.read_game()?);
+        }
+    }
+
+    fn read_game(&mut self) -> Result<Pgn, PgnParseError> {
+        let mut pgn = Pgn {
+            tags: Vec::new(),
+            plys: Vec::new(),
+            result: None,
+            tag_positions: Vec::new(),
+        };
+
+        // Tag pair section.
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some('[') => {
+                    pgn.tag_positions.push((self.line, self.column));
+                    pgn.tags.push(self.read_tag()?);
+                },
+                Some('%') if self.column == 1 => {
+                    self.read_while(|char| char != '\n');
+                },
+                _ => break,
+            }
+        }
+
+        // Movetext section, each open variation has its own line of plys.
+        let mut lines: Vec<(Vec<PgnPly>, usize, usize)> = vec![(Vec::new(), self.line, self.column)];
+        let mut comments = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            let (line, column) = (self.line, self.column);
+
+            let char = match self.peek() {
+                Some(char) => char,
+                None => break,
+            };
+
+            match char {
+                // The next game starts without a termination marker.
+                '[' if lines.len() == 1 => break,
+                '%' if column == 1 => {
+                    self.read_while(|char| char != '\n');
+                },
+                '{' => {
+                    self.next();
+                    let comment = self.read_while(|char| char != '}');
+                    if self.next().is_none() {
+                        return Err(Self::error_at(line, column, PgnErrorKind::UnterminatedComment));
+                    }
+                    comments.push(comment.trim().to_string());
+                },
+                ';' => {
+                    self.next();
+                    comments.push(self.read_while(|char| char != '\n').trim().to_string());
+                },
+                '(' => {
+                    self.next();
+                    if lines.last().is_none_or(|(plys, _, _)| plys.is_empty()) {
+                        return Err(Self::error_at(line, column, PgnErrorKind::UnbalancedVariation));
+                    }
+                    Self::attach_comments(&mut lines, &mut comments);
+                    lines.push((Vec::new(), line, column));
+                },
+                ')' => {
+                    self.next();
+                    if lines.len() == 1 {
+                        return Err(Self::error_at(line, column, PgnErrorKind::UnbalancedVariation));
+                    }
+                    Self::attach_comments(&mut lines, &mut comments);
+                    let (variation, _, _) = lines.pop().unwrap_or_default();
+                    if let Some(ply) = lines.last_mut().and_then(|(plys, _, _)| plys.last_mut()) {
+                        ply.variations.push(variation);
+                    }
+                },
+                '$' => {
+                    self.next();
+                    let nag = self.read_while(|char| char.is_ascii_digit()).parse()
+                        .map_err(|_| Self::error_at(line, column, PgnErrorKind::InvalidNag))?;
+                    Self::attach_comments(&mut lines, &mut comments);
+                    match lines.last_mut().and_then(|(plys, _, _)| plys.last_mut()) {
+                        Some(ply) => ply.nags.push(nag),
+                        None => return Err(Self::error_at(line, column, PgnErrorKind::InvalidNag)),
+                    }
+                },
+                '*' => {
+                    self.next();
+                    pgn.result = Some("*".to_string());
+                    break;
+                },
+                '.' => {
+                    self.next();
+                },
+                _ if char.is_alphanumeric() => {
+                    let symbol = self.read_while(|char| char.is_alphanumeric() || "_+#=:-/".contains(char));
+
+                    if let "1-0" | "0-1" | "1/2-1/2" = symbol.as_str() {
+                        pgn.result = Some(symbol);
+                        break;
+                    }
+
+                    // Move numbers, the dots following them are skipped separately.
+                    if symbol.chars().all(|char| char.is_ascii_digit()) {
+                        continue;
+                    }
+
+                    let nag = match self.read_while(|char| char == '!' || char == '?').as_str() {
+                        "" => None,
+                        "!" => Some(1),
+                        "?" => Some(2),
+                        "!!" => Some(3),
+                        "??" => Some(4),
+                        "!?" => Some(5),
+                        "?!" => Some(6),
+                        _ => return Err(Self::error_at(line, column + symbol.len(), PgnErrorKind::InvalidNag)),
+                    };
+
+                    Self::attach_comments(&mut lines, &mut comments);
+
+                    if let Some((plys, _, _)) = lines.last_mut() {
+                        plys.push(PgnPly {
+                            san: symbol,
+                            nags: nag.into_iter().collect(),
+                            comments_before: std::mem::take(&mut comments),
+                            comments: Vec::new(),
+                            variations: Vec::new(),
+                            line,
+                            column,
+                        });
+                    }
+                },
+                _ => return Err(self.error(PgnErrorKind::UnexpectedCharacter(char))),
+            }
+        }
+
+        if lines.len() > 1 {
+            let (_, line, column) = lines[lines.len() - 1];
+            return Err(Self::error_at(line, column, PgnErrorKind::UnbalancedVariation));
+        }
+
+        Self::attach_comments(&mut lines, &mut comments);
+
+        pgn.plys = lines.pop().map(|(plys, _, _)| plys).unwrap_or_default();
+
+        Ok(pgn)
+    }
+
+    /// Attaches pending comments to the last ply of the current line, 
+    /// or keeps them for the next ply if the line has no plys yet.
+    fn attach_comments(lines: &mut [(Vec<PgnPly>, usize, usize)], comments: &mut Vec<String>) {
+        if let Some(ply) = lines.last_mut().and_then(|(plys, _, _)| plys.last_mut()) {
+            ply.comments.append(comments);
+        }
+    }
+
+    /// Reads a tag pair: [Name "Value"], where the value may contain escaped quotes and backslashes.
+    fn read_tag(&mut self) -> Result<(String, String), PgnParseError> {
+        let (line, column) = (self.line, self.column);
+        let unterminated = || Self::error_at(line, column, PgnErrorKind::UnterminatedTag);
+
+        self.next();
+        self.skip_whitespace();
+
+        let name = self.read_while(|char| char.is_alphanumeric() || char == '_');
+
+        self.skip_whitespace();
+
+        match self.next() {
+            Some('"') => (),
+            Some(char) => return Err(self.error(PgnErrorKind::UnexpectedCharacter(char))),
+            None => return Err(unterminated()),
+        }
+
+        let mut value = String::new();
+
+        loop {
+            match self.next() {
+                Some('"') => break,
+                Some('\\') => match self.next() {
+                    Some(char) => value.push(char),
+                    None => return Err(unterminated()),
+                },
+                Some('\n') | None => return Err(unterminated()),
+                Some(char) => value.push(char),
+            }
+        }
+
+        self.skip_whitespace();
+
+        match self.next() {
+            Some(']') => Ok((name, value)),
+            Some(char) => Err(self.error(PgnErrorKind::UnexpectedCharacter(char))),
+            None => Err(unterminated()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: &str = r#"[Event "Casual \"Blitz\" Game"]
+[Site "?"]
+[Date "2023.10.01"]
+[Round "-"]
+[White "Player, A"]
+[Black "Player, B"]
+[Result "1-0"]
+[Annotator "C"]
+
+{Opening comment.} 1. e4 e5 2. Nf3 $1 Nc6 (2... d6 {Philidor.} 3. d4 (3. Bc4)) 3. Bb5!? a6
+; Rest of line comment
+4.Ba4 Nf6 5. O-O 1-0"#;
+
+    #[test]
+    fn parse() {
+        let games = Pgn::parse(GAME).unwrap();
+
+        assert_eq!(games.len(), 1);
+
+        let pgn = &games[0];
+
+        assert_eq!(pgn.tags.len(), 8);
+        assert_eq!(pgn.tag("Event"), Some("Casual \"Blitz\" Game"));
+        assert_eq!(pgn.tag("Annotator"), Some("C"));
+        assert_eq!(pgn.result.as_deref(), Some("1-0"));
+
+        let sans: Vec<&str> = pgn.plys.iter().map(|ply| ply.san.as_str()).collect();
+        assert_eq!(sans, ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "Ba4", "Nf6", "O-O"]);
+
+        assert_eq!(pgn.plys[0].comments_before, ["Opening comment."]);
+        assert_eq!(pgn.plys[2].nags, [1]);
+        assert_eq!(pgn.plys[4].nags, [5]);
+        assert_eq!(pgn.plys[5].comments, ["Rest of line comment"]);
+
+        let variation = &pgn.plys[3].variations[0];
+        assert_eq!(variation.len(), 2);
+        assert_eq!(variation[0].san, "d6");
+        assert_eq!(variation[0].comments, ["Philidor."]);
+        assert_eq!(variation[1].variations[0][0].san, "Bc4");
+
+        let game = pgn.to_game().unwrap();
+        assert_eq!(game.to_fen(), "r1bqkb1r/1ppp1ppp/p1n2n2/4p3/B3P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 3 5");
+    }
+
+    #[test]
+    fn multiple_games() {
+        let pgn = "[Event \"A\"]\n\n1. e4 *\n\n[Event \"B\"]\n\n1. d4 d5 1/2-1/2\n";
+        let games = Pgn::parse(pgn).unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].result.as_deref(), Some("*"));
+        assert_eq!(games[1].tag("Event"), Some("B"));
+        assert_eq!(games[1].plys.len(), 2);
+    }
+
+    #[test]
+    fn set_up() {
+        let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/R3K3 w Q - 0 1\"]\n\n1. O-O-O Kf7 *";
+        let game = Game::from_pgn(pgn).unwrap();
+
+        assert_eq!(game.to_fen(), "8/5k2/8/8/8/8/8/2KR4 w - - 2 2");
+    }
+
+    #[test]
+    fn write() {
+        let mut game = crate::default_game();
+
+        for san in ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "Ba4", "Nf6", "O-O", "Be7", "Re1", "b5", "Bb3", "d6"] {
+            game.ply_san(san).unwrap();
+        }
+
+        for san in ["c3", "O-O", "h3", "Nb8", "d4", "Nbd7", "c4", "c6", "cxb5", "axb5", "Nc3", "Bb7"] {
+            game.ply_san(san).unwrap();
+        }
+
+        let pgn = game.to_pgn_with_tags(&[("White", "A"), ("Black", "B"), ("Annotator", "C")]);
+
+        assert!(pgn.starts_with("[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n"));
+        assert!(pgn.contains("[White \"A\"]\n[Black \"B\"]\n[Result \"*\"]\n[Annotator \"C\"]\n\n1. e4 e5 2. Nf3"));
+        assert!(!pgn.contains("FEN"));
+        assert!(pgn.ends_with("Bb7 *\n"));
+        assert!(pgn.lines().all(|line| line.len() <= 80));
+        assert!(pgn.lines().count() > 10);
+
+        let copy = Game::from_pgn(&pgn).unwrap();
+
+        assert_eq!(copy.to_fen(), game.to_fen());
+    }
+
+    #[test]
+    fn write_set_up() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 b Q - 0 7").unwrap();
+
+        game.ply_san("Kd7").unwrap();
+        game.ply_san("Ra7+").unwrap();
+        game.ply_san("Kc6").unwrap();
+
+        let pgn = game.to_pgn_with_tags(&[("Event", "Endgame \"study\"")]);
+
+        assert!(pgn.contains("[Event \"Endgame \\\"study\\\"\"]\n"));
+        assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/R3K3 b Q - 0 7\"]\n"));
+        assert!(pgn.ends_with("\n7... Kd7 8. Ra7+ Kc6 *\n"));
+
+        let pgn = Pgn::parse(&pgn).unwrap();
+
+        assert_eq!(pgn[0].tag("Event"), Some("Endgame \"study\""));
+        assert_eq!(pgn[0].to_game().unwrap().to_fen(), game.to_fen());
+    }
+
+    #[test]
+    fn write_result() {
+        let mut game = crate::default_game();
+
+        for san in ["f3", "e5", "g4", "Qh4#"] {
+            game.ply_san(san).unwrap();
+        }
+
+        assert!(game.to_pgn().contains("[Result \"0-1\"]"));
+        assert!(game.to_pgn().ends_with("1. f3 e5 2. g4 Qh4# 0-1\n"));
+    }
+
+    #[test]
+    fn errors() {
+        let error = Game::from_pgn("[Event \"?\"]\n\n1. e4 e5\n2. Ke3 *").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 4));
+        assert!(matches!(error.kind, PgnErrorKind::InvalidPly(ref san, _) if san == "Ke3"));
+
+        let error = Game::from_pgn("1. e4 (1. d4 (1. Nf3) e5 *").unwrap_err();
+        assert_eq!((error.line, error.column, error.kind), (1, 7, PgnErrorKind::UnbalancedVariation));
+
+        let error = Game::from_pgn("1. e4 {Never closed").unwrap_err();
+        assert_eq!((error.line, error.column, error.kind), (1, 7, PgnErrorKind::UnterminatedComment));
+
+        let error = Game::from_pgn("[Event \"?]\n1. e4 *").unwrap_err();
+        assert_eq!((error.line, error.column, error.kind), (1, 1, PgnErrorKind::UnterminatedTag));
+
+        let error = Game::from_pgn("[FEN \"8/8 w - - 0 1\"]\n1. e4 *").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert!(matches!(error.kind, PgnErrorKind::InvalidFen(_)));
+
+        // Variations are verified from the position before the ply they replace.
+        let error = Game::from_pgn("1. e4 e5 (1... Nf3) *").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 16));
+    }
+}
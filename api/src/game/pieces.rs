@@ -0,0 +1,79 @@
+use crate::board::*;
+
+use super::Game;
+
+impl Game {
+    /// Returns every piece on the board with its square, ordered from a1 to h8.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use api::{Color, Piece, PieceKind, Square};
+    /// 
+    /// let game = api::fen_game("4k3/8/8/8/8/8/3P4/4K3 w - - 0 1").unwrap();
+    /// 
+    /// assert_eq!(game.pieces(), vec![
+    ///     (Square::E1, Piece::new(Color::White, PieceKind::King)),
+    ///     (Square::D2, Piece::new(Color::White, PieceKind::Pawn)),
+    ///     (Square::E8, Piece::new(Color::Black, PieceKind::King)),
+    /// ]);
+    /// ```
+    pub fn pieces(&self) -> Vec<(Square, Piece)> {
+        Squares(self.board.occupied())
+            .filter_map(|square| {
+                let square = Square::ALL[square];
+                let piece = self.board.get_tiles()[square.mailbox()].piece()?;
+
+                Some((square, piece))
+            })
+            .collect()
+    }
+
+    /// Returns the squares of every piece of a color and type, ordered from a1 to h8.
+    pub fn pieces_of(&self, color: Color, kind: PieceKind) -> Vec<Square> {
+        Squares(self.board.bitboard(Piece::new(color, kind).into()))
+            .map(|square| Square::ALL[square])
+            .collect()
+    }
+
+    /// Returns the square of a player's king.
+    /// 
+    /// Games always have one king of each color, so this only returns None for positions that are being set up.
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.king_index(color).map(Square::from_mailbox)
+    }
+
+    /// Returns the total material value (see [`PieceKind::value`]) of a player's pieces, 39 in the starting position.
+    pub fn material(&self, color: Color) -> u32 {
+        PieceKind::ALL.iter()
+            .map(|kind| self.board.bitboard(Piece::new(color, *kind).into()).count_ones() * kind.value())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries() {
+        let mut game = crate::default_game();
+
+        assert_eq!(game.pieces().len(), 32);
+        assert_eq!(game.pieces_of(Color::Black, PieceKind::Knight), vec![Square::B8, Square::G8]);
+        assert_eq!(game.king_square(Color::White), Some(Square::E1));
+        assert_eq!(game.material(Color::White), 39);
+
+        for ply in ["e2e4", "d7d5", "e4d5", "d8d5"] {
+            game.ply_str(ply).unwrap();
+        }
+
+        assert_eq!(game.pieces().len(), 30);
+        assert_eq!(game.material(Color::White), 38);
+        assert_eq!(game.material(Color::Black), 38);
+        assert_eq!(game.pieces_of(Color::Black, PieceKind::Queen), vec![Square::D5]);
+
+        game.ply_str("e1e2").unwrap();
+        assert_eq!(game.king_square(Color::White), Some(Square::E2));
+    }
+}
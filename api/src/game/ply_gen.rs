@@ -1,353 +1,267 @@
 use crate::board::*;
 
-use super::Game;
+use super::{Game, SanParseError};
 
-#[derive(Debug)]
+/// Represents the reason a ply was rejected.
+#[derive(Debug, Clone, PartialEq)]
 pub enum PlyError {
+    /// The ply could not be read, or a position is outside the board.
     InvalidPly,
-    Unknown,
+    InvalidSan(SanParseError),
+    /// There is no piece on the origin square.
+    NoPiece(Square),
+    /// The piece on the origin square belongs to the opponent.
+    NotYourPiece(Square),
+    /// The piece on the origin square cannot move to the destination square.
+    IllegalDestination(Square, Square),
+    /// The ply would leave the player's own king in check.
+    LeavesKingInCheck,
+    /// A pawn reaching the last rank without a chosen promotion piece.
+    MissingPromotion,
+    /// A promotion piece that is not a queen, rook, bishop or knight of the player's color,
+    /// or a promotion piece given for a ply that is not a promotion.
+    InvalidPromotion(Tile),
+    GameOver,
+    Board(BoardError),
 }
 
 impl std::fmt::Display for PlyError {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(formatter, "invalid ply")
+        match self {
+            PlyError::InvalidPly => write!(formatter, "invalid ply: could not read the ply"),
+            PlyError::InvalidSan(e) => write!(formatter, "invalid ply: {e}"),
+            PlyError::NoPiece(square) => write!(formatter, "invalid ply: there is no piece on {square}"),
+            PlyError::NotYourPiece(square) => write!(formatter, "invalid ply: the piece on {square} belongs to the opponent"),
+            PlyError::IllegalDestination(origin, destination) => write!(formatter, "invalid ply: the piece on {origin} cannot move to {destination}"),
+            PlyError::LeavesKingInCheck => write!(formatter, "invalid ply: the king would be left in check"),
+            PlyError::MissingPromotion => write!(formatter, "invalid ply: a promotion piece must be chosen"),
+            PlyError::InvalidPromotion(tile) => write!(formatter, "invalid ply: cannot promote to '{tile}'"),
+            PlyError::GameOver => write!(formatter, "invalid ply: the game is over"),
+            PlyError::Board(e) => write!(formatter, "invalid ply: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PlyError {}
+
+impl From<BoardError> for PlyError {
+    fn from(error: BoardError) -> Self {
+        PlyError::Board(error)
     }
 }
 
+/// Represents a single move made by one player, from the origin square to the destination square (see [`Square`]).
+/// 
+/// Pawns reaching the last rank carry the piece they are promoted to, 
+/// all other plys have no promotion.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Ply {
-    pub origin: usize,
-    pub destination: usize,
+    pub origin: Square,
+    pub destination: Square,
+    pub promotion: Option<Tile>,
 }
 
 impl std::fmt::Display for Ply {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut string = String::new();
 
-        let origin_string = Board::index_to_string(self.origin);
-        let destination_string = Board::index_to_string(self.destination);
+        let origin_string = self.origin.to_string().to_uppercase();
+        let destination_string = self.destination.to_string().to_uppercase();
 
         string.push_str(origin_string.as_str());
         string.push(' ');
         string.push_str(destination_string.as_str());
 
+        if let Some(promotion) = self.promotion {
+            string.push('=');
+            string.push_str(promotion.to_string().to_uppercase().as_str());
+        }
+
         write!(f, "{}", string)
     }
 }
 
-impl Ply {
-    pub(crate) fn to_string(&self) -> String {
-        let mut string = String::new();
+impl Game {
+    /// Returns Ok if a ply is legal, or the reason it is not (see [`PlyError`]).
+    pub(crate) fn check_ply(&self, ply: &Ply) -> Result<(), PlyError> {
+        if self.plys.contains(ply) {
+            return Ok(());
+        }
 
-        let origin_string = Board::index_to_string(self.origin);
-        let destination_string = Board::index_to_string(self.destination);
+        match self.board.get_tiles()[ply.origin.mailbox()].piece() {
+            None => return Err(PlyError::NoPiece(ply.origin)),
+            Some(piece) if piece.color != self.player => return Err(PlyError::NotYourPiece(ply.origin)),
+            Some(_) => (),
+        }
 
-        string.push_str(origin_string.as_str());
-        string.push(' ');
-        string.push_str(destination_string.as_str());
+        let same_squares = |other: &Ply| other.origin == ply.origin && other.destination == ply.destination;
+
+        // The squares are right, so the promotion piece is wrong.
+        if self.plys.iter().any(same_squares) {
+            return match ply.promotion {
+                Some(promotion) => Err(PlyError::InvalidPromotion(promotion)),
+                None => Err(PlyError::MissingPromotion),
+            };
+        }
 
-        string
+        match self.gen_pseudo_legal_plys().iter().any(same_squares) {
+            true => Err(PlyError::LeavesKingInCheck),
+            false => Err(PlyError::IllegalDestination(ply.origin, ply.destination)),
+        }
     }
-}
 
-impl Game {
     pub(crate) fn gen_plys(&mut self) {
-        let mut plys = self.gen_pseudo_legal_plys();
+        let plys = self.gen_pseudo_legal_plys();
 
-        self.plys = plys.clone();
+        self.plys = self.gen_legal_plys(plys);
 
-        plys = self.gen_legal_plys(plys);
-
-        self.plys = plys.clone();
+        // The en passant target only counts if the capture is possible, which is known now.
+        self.hash ^= self.en_passant_hash;
+        self.en_passant_hash = self.en_passant_capture()
+            .map_or(0, |target| en_passant_key(Board::get_pos(target).file as usize));
+        self.hash ^= self.en_passant_hash;
     }
 
-    fn gen_pawn_plys(&self, origin: usize) -> Vec<Ply> {
-        let mut plys: Vec<Ply> = Vec::new();
+    /// Adds a ply from the origin square to each square of a set of destinations.
+    fn push_plys(origin: usize, destinations: Bitboard, plys: &mut Vec<Ply>) {
+        let origin = Square::ALL[origin];
 
-        let can_double_push = match self.player {
-            Color::White => Board::get_pos(origin).rank == 1,
-            Color::Black => Board::get_pos(origin).rank == 6,
-        };
-
-        let mut destination = match self.player {
-            Color::White => origin + 10,
-            Color::Black => origin - 10,
-        };
-
-        match self.board.get_tile(destination) {
-            Some(tile) => match tile {
-                tile!(.) => {
-                    plys.push(Ply{ origin, destination });
-                    if can_double_push {
-                        match self.player {
-                            Color::White => destination += 10,
-                            Color::Black => destination -= 10,
-                        };
-                        match self.board.get_tile(destination) {
-                            Some(tile) => match tile {
-                                tile!(.) => {
-                                    plys.push(Ply{ origin, destination })
-                                },
-                                _ => (),
-                            } 
-                            _ => (),
-                        }
-                    }
-                },
-                _ => (),
-            } 
-            _ => (),
+        for destination in Squares(destinations) {
+            plys.push(Ply { origin, destination: Square::ALL[destination], promotion: None });
         }
+    }
+
+    fn gen_pawn_plys(&self, origin: usize, plys: &mut Vec<Ply>) {
+        let empty = !self.board.occupied();
 
-        destination = match self.player {
-            Color::White => origin + 9,
-            Color::Black => origin - 11,
+        let (push, double_push, start_rank, last_rank, promotions) = match self.player {
+            Color::White => (origin + 8, origin + 16, 1, 7, [tile!(Q), tile!(R), tile!(B), tile!(N)]),
+            Color::Black => (origin - 8, origin.wrapping_sub(16), 6, 0, [tile!(q), tile!(r), tile!(b), tile!(n)]),
         };
 
-        match self.board.get_tile(destination) {
-            Some(tile) => match tile {
-                tile!(white) => if self.player == Color::Black {
-                    plys.push(Ply{ origin, destination })
-                }
-                tile!(black) => if self.player == Color::White {
-                    plys.push(Ply{ origin, destination })
-                }
-                _ => (),
-            },
-            None => (),
-        }
+        let mut destinations: Bitboard = 0;
 
-        destination = match self.player {
-            Color::White => origin + 11,
-            Color::Black => origin - 9,
-        };
+        // Push and double push, both onto empty tiles.
+        if empty & 1 << push != 0 {
+            destinations |= 1 << push;
 
-        match self.board.get_tile(destination) {
-            Some(tile) => match tile {
-                tile!(white) => if self.player == Color::Black {
-                    plys.push(Ply{ origin, destination })
-                }
-                tile!(black) => if self.player == Color::White {
-                    plys.push(Ply{ origin, destination })
-                }
-                _ => (),
-            },
-            None => (),
+            if origin / 8 == start_rank && empty & 1 << double_push != 0 {
+                destinations |= 1 << double_push;
+            }
         }
-        
-        // Push X
-        // Double Push X
-        // Capture X
-        // Promotion
-        // En passant
-        /*  . +20 .
-         * +9 +10+11
-         *  .  O  .
-         *  .  .  .
-         */
-        /*  .  .  .
-         *  .  O  .
-         * -11-10-9
-         *  . -20 .
-         */
 
-        plys
-    }
+        // Captures, including en passant onto the tile passed by the opponent's double push.
+        let mut targets = self.board.occupancy(self.opponent());
+        if let Some(index) = self.en_passant {
+            targets |= 1 << square_of(index);
+        }
 
-    fn gen_bishop_plys(&self, origin: usize) -> Vec<Ply> {
-        let bishop_delta = [-11, -9, 9, 11];
-        /* +9  . +11
-         *  .  O  .
-         * -11 . -9
-         */
+        destinations |= PAWN_ATTACKS[self.player as usize][origin] & targets;
 
-        let mut plys: Vec<Ply> = Vec::new();
+        // Promotion, a pawn reaching the last rank is replaced by one of four pieces.
+        for destination in Squares(destinations) {
+            let ply = Ply { origin: Square::ALL[origin], destination: Square::ALL[destination], promotion: None };
 
-        for direction in bishop_delta {
-            let mut i = 1;
-            'slide: loop {
-                let destination = (direction * i + origin as isize) as usize;
-                match self.board.get_tile(destination) {
-                    Some(tile) => match tile {
-                        tile!(white) => match self.player {
-                            Color::White => break 'slide,
-                            Color::Black =>  {
-                                plys.push(Ply{ origin, destination });
-                                break 'slide
-                            },
-                        },
-                        tile!(black) => match self.player {
-                            Color::White => {
-                                plys.push(Ply{ origin, destination });
-                                break 'slide
-                            },
-                            Color::Black => break 'slide,
-                        },
-                        tile!(.) => plys.push(Ply{ origin, destination }),
-                        tile!(_) => break 'slide,
-                    } 
-                    _ => (),
-                }
-                i += 1;
+            match destination / 8 == last_rank {
+                true => plys.extend(promotions.map(|promotion| Ply { promotion: Some(promotion), ..ply })),
+                false => plys.push(ply),
             }
         }
-
-        plys
     }
 
-    fn gen_knight_plys(&self, origin: usize) -> Vec<Ply> {
-        let knight_delta = [-21, -19, -12, -8, 8, 12, 19, 21];
-        /*  . +19 . +21 .
-         * +8  .  .  . +12
-         *  .  .  O  .  .
-         * -12 .  .  . -8
-         *  . -21 . -19 .
-         */
+    fn gen_knight_plys(&self, origin: usize, plys: &mut Vec<Ply>) {
+        let own = self.board.occupancy(self.player);
 
-        let mut plys: Vec<Ply> = Vec::new();
+        Self::push_plys(origin, KNIGHT_ATTACKS[origin] & !own, plys);
+    }
 
-        for destination in knight_delta.iter().map(|i| (i + origin as isize) as usize) {
-            match self.board.get_tile(destination) {
-                Some(tile) => match tile {
-                    tile!(white) => match self.player {
-                        Color::White => (),
-                        Color::Black => plys.push(Ply{ origin, destination }),
-                    },
-                    tile!(black) => match self.player {
-                        Color::White => plys.push(Ply{ origin, destination }),
-                        Color::Black => (),
-                    },
-                    tile!(.) => plys.push(Ply{ origin, destination }),
-                    tile!(_) => (),
-                } 
-                _ => (),
-            }
-        }
+    fn gen_bishop_plys(&self, origin: usize, plys: &mut Vec<Ply>) {
+        let own = self.board.occupancy(self.player);
 
-        plys
+        Self::push_plys(origin, bishop_attacks(origin, self.board.occupied()) & !own, plys);
     }
 
-    fn gen_rook_plys(&self, origin: usize) -> Vec<Ply> {
-        let rook_delta = [-10, -1, 1, 10];
-        /*  . +10 .
-         * -1  O +1
-         *  . -10 .
-         */
-
-        let mut plys: Vec<Ply> = Vec::new();
+    fn gen_rook_plys(&self, origin: usize, plys: &mut Vec<Ply>) {
+        let own = self.board.occupancy(self.player);
 
-        for direction in rook_delta {
-            let mut i = 1;
-            'slide: loop {
-                let destination = (direction * i + origin as isize) as usize;
-                match self.board.get_tile(destination) {
-                    Some(tile) => match tile {
-                        tile!(white) => match self.player {
-                            Color::White => break 'slide,
-                            Color::Black => {
-                                plys.push(Ply{ origin, destination });
-                                break 'slide
-                            },
-                        },
-                        tile!(black) => match self.player {
-                            Color::White => {
-                                plys.push(Ply{ origin, destination });
-                                break 'slide
-                            },
-                            Color::Black => break 'slide,
-                        },
-                        tile!(.) => plys.push(Ply{ origin, destination }),
-                        tile!(_) => break 'slide,
-                    } 
-                    _ => (),
-                }
-                i += 1;
-            }
-        }
+        Self::push_plys(origin, rook_attacks(origin, self.board.occupied()) & !own, plys);
+    }
 
-        plys
+    fn gen_queen_plys(&self, origin: usize, plys: &mut Vec<Ply>) {
+        self.gen_bishop_plys(origin, plys);
+        self.gen_rook_plys(origin, plys);
     }
 
-    fn gen_queen_plys(&self, origin: usize) -> Vec<Ply> {
-        let mut plys: Vec<Ply> = Vec::new();
+    fn gen_king_plys(&self, origin: usize, plys: &mut Vec<Ply>) {
+        let own = self.board.occupancy(self.player);
 
-        plys.append(&mut self.gen_bishop_plys(origin));
-        plys.append(&mut self.gen_rook_plys(origin));
+        Self::push_plys(origin, KING_ATTACKS[origin] & !own, plys);
 
-        plys
-    }
+        let origin = index_of(origin);
 
-    fn gen_king_plys(&self, origin: usize) -> Vec<Ply> {
-        let king_delta = [-11, -10, -9, -1, 1, 9, 10, 11];
+        let (rights, king_origin, opponent) = match self.player {
+            Color::White => ((self.castling.white_king_side, self.castling.white_queen_side), 25, Color::Black),
+            Color::Black => ((self.castling.black_king_side, self.castling.black_queen_side), 95, Color::White),
+        };
 
-        let mut plys: Vec<Ply> = Vec::new();
+        // Castling requires the king and rook on their original tiles, empty tiles between them,
+        // and that the king is not in check, does not pass through check and does not end in check.
+        if origin == king_origin && (rights.0 || rights.1) && !self.is_attacked(origin, opponent) {
+            let rook = match self.player {
+                Color::White => tile!(R),
+                Color::Black => tile!(r),
+            };
+
+            let is_empty = |index: usize| self.board.get_tile(index) == Some(&tile!(.));
+            let is_safe = |index: usize| !self.is_attacked(index, opponent);
+
+            if rights.0 
+                && self.board.get_tile(origin + 3) == Some(&rook)
+                && is_empty(origin + 1) && is_empty(origin + 2)
+                && is_safe(origin + 1) && is_safe(origin + 2) {
+                plys.push(Ply{ origin: Square::from_mailbox(origin), destination: Square::from_mailbox(origin + 2), promotion: None });
+            }
 
-        for destination in king_delta.iter().map(|i| (i + origin as isize) as usize) {
-            match self.board.get_tile(destination) {
-                Some(tile) => match tile {
-                    tile!(white) => match self.player {
-                        Color::White => (),
-                        Color::Black => plys.push(Ply{ origin, destination }),
-                    },
-                    tile!(black) => match self.player {
-                        Color::White => plys.push(Ply{ origin, destination }),
-                        Color::Black => (),
-                    },
-                    tile!(.) => plys.push(Ply{ origin, destination }),
-                    tile!(_) => (),
-                } 
-                _ => (),
+            if rights.1 
+                && self.board.get_tile(origin - 4) == Some(&rook)
+                && is_empty(origin - 1) && is_empty(origin - 2) && is_empty(origin - 3)
+                && is_safe(origin - 1) && is_safe(origin - 2) {
+                plys.push(Ply{ origin: Square::from_mailbox(origin), destination: Square::from_mailbox(origin - 2), promotion: None });
             }
         }
-
-        // Step
-        // Castling
-        /* +9 +10+11
-         * -1  O +1
-         * -11-10-9
-         */
-
-        plys
     }
 
     fn gen_pseudo_legal_plys(&self) -> Vec<Ply> {
         let mut plys: Vec<Ply> = Vec::new();
 
-        match self.player {
-            Color::White => for (index, tile) in self.board.get_tiles().iter().enumerate() {
-                match tile {
-                    tile!(P) => plys.append(&mut self.gen_pawn_plys(index)),
-                    tile!(N) => plys.append(&mut self.gen_knight_plys(index)),
-                    tile!(B) => plys.append(&mut self.gen_bishop_plys(index)),
-                    tile!(R) => plys.append(&mut self.gen_rook_plys(index)),
-                    tile!(Q) => plys.append(&mut self.gen_queen_plys(index)),
-                    tile!(K) => plys.append(&mut self.gen_king_plys(index)),
-                    _ => (),
-                }
-            }
-            Color::Black => for (index, tile) in self.board.get_tiles().iter().enumerate() {
-                match tile {
-                    tile!(p) => plys.append(&mut self.gen_pawn_plys(index)),
-                    tile!(n) => plys.append(&mut self.gen_knight_plys(index)),
-                    tile!(b) => plys.append(&mut self.gen_bishop_plys(index)),
-                    tile!(r) => plys.append(&mut self.gen_rook_plys(index)),
-                    tile!(q) => plys.append(&mut self.gen_queen_plys(index)),
-                    tile!(k) => plys.append(&mut self.gen_king_plys(index)),
-                    _ => (),
-                }
+        for origin in Squares(self.board.occupancy(self.player)) {
+            match self.board.get_tile(index_of(origin)) {
+                Some(Tile::Pawn(_)) => self.gen_pawn_plys(origin, &mut plys),
+                Some(Tile::Knight(_)) => self.gen_knight_plys(origin, &mut plys),
+                Some(Tile::Bishop(_)) => self.gen_bishop_plys(origin, &mut plys),
+                Some(Tile::Rook(_)) => self.gen_rook_plys(origin, &mut plys),
+                Some(Tile::Queen(_)) => self.gen_queen_plys(origin, &mut plys),
+                Some(Tile::King(_)) => self.gen_king_plys(origin, &mut plys),
+                _ => (),
             }
         }
 
         plys
     }
 
-    fn gen_legal_plys(&self, pseudo_legal_plys: Vec<Ply>) -> Vec<Ply> {
+    /// Returns the index of the king of the given color, if it is on the board.
+    pub(crate) fn king_index(&self, color: Color) -> Option<usize> {
+        Squares(self.board.bitboard(Tile::King(color))).next().map(index_of)
+    }
+
+    fn gen_legal_plys(&mut self, pseudo_legal_plys: Vec<Ply>) -> Vec<Ply> {
         let mut legal_plys = vec![];
 
-        println!("{:?}", self);
+        let king = self.king_index(self.player);
 
         for ply in pseudo_legal_plys {
-            if self.is_legal_ply(ply) {
+            if self.is_legal_ply(ply, king) {
                 legal_plys.push(ply);
             }
         }
@@ -355,73 +269,27 @@ impl Game {
         legal_plys
     }
 
-    fn is_legal_ply(&self, ply: Ply) -> bool {
-        let mut game = self.clone();
-        let origin = Board::get_pos(ply.origin);
-        let destination = Board::get_pos(ply.destination);
-
-        // Make ply and update state of the copied game.
-        if let Err(_) = game.test_ply(origin, destination) {
-            return false;
-        }
-
-        println!("{:?}", game);
-
-        let opponent_king_tile = match game.player {
-            Color::White => tile!(k),
-            Color::Black => tile!(K),
-        };
-
-        let mut opponent_king_pos = Pos { rank: -1, file: -1 };
-        'king_search: for rank in 0..8 {
-            for file in 0..8 {
-                opponent_king_pos = Pos { rank, file };
-                // Bad code.
-                if opponent_king_tile == *game.get_tile_from_pos(opponent_king_pos).unwrap() {
-                    break 'king_search
-                }
-            }
-        }
-        
-        for ply in game.get_plys() {
-            if Board::get_pos(ply.destination) == opponent_king_pos {
-                return false
-            }
-        }
-
-        true
-    }
+    /// Returns true if a pseudo-legal ply does not leave the king of the current player attacked.
+    /// 
+    /// The ply is made and unmade on the board in place, the rest of the game is left untouched.
+    fn is_legal_ply(&mut self, ply: Ply, king: Option<usize>) -> bool {
+        let (origin, destination) = (ply.origin.mailbox(), ply.destination.mailbox());
 
-    fn test_ply(&mut self, origin: Pos, destination: Pos) -> Result<(), PlyError> {
-        let origin_index = Board::get_index(origin);
-        let destination_index = Board::get_index(destination);
-        let ply = Ply {
-            origin: origin_index,
-            destination: destination_index,
+        let king = match king {
+            Some(index) if index == origin => destination,
+            Some(index) => index,
+            None => return true,
         };
 
-        if !(self.plys.contains(&ply)) {
-            println!("Invalid Ply: {}", ply);
-            return Err(PlyError::InvalidPly);
-        }
-
-        let tile = match self.board.rem_tile(origin_index) {
-            Ok(t) => t,
-            Err(_) => return Err(PlyError::Unknown),
+        let undo = match self.board.make_ply(origin, destination, ply.promotion, self.en_passant) {
+            Ok(undo) => undo,
+            Err(_) => return false,
         };
 
-        match self.board.set_tile(destination_index, tile) {
-            Ok(_) => (),
-            Err(_) => return Err(PlyError::Unknown),
-        }
-
-        self.player = match self.player {
-            Color::White => Color::Black,
-            Color::Black => Color::White,
-        };
+        let is_legal = !self.is_attacked(king, self.opponent());
 
-        self.plys = self.gen_pseudo_legal_plys();
+        self.board.unmake_ply(&undo);
 
-        return Ok(());
+        is_legal
     }
 }
\ No newline at end of file
@@ -0,0 +1,129 @@
+use crate::board::*;
+
+use super::{Game, Ply, PlyRecord};
+
+/// Represents the kinds of plys that move pieces in a special way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlyKind {
+    /// A piece moving to an empty tile or capturing the piece on it.
+    Normal,
+    /// A pawn moving two tiles forward from its starting rank.
+    DoublePush,
+    /// A pawn capturing a pawn which just made a double push, removing it from the tile it passed.
+    EnPassant,
+    /// The king moving two tiles towards the rook on the h-file, which moves to the tile it passed.
+    KingSideCastle,
+    /// The king moving two tiles towards the rook on the a-file, which moves to the tile it passed.
+    QueenSideCastle,
+    /// A pawn reaching the last rank, replaced by the promotion piece of the ply.
+    Promotion,
+}
+
+/// Represents a single tile changed by a ply, with the tile before and after the ply was made.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileChange {
+    pub square: Square,
+    pub before: Tile,
+    pub after: Tile,
+}
+
+/// Represents a ply that has been made (see [`Game::ply`]), 
+/// with what moved, what was captured and every tile that changed.
+/// 
+/// The changes list the origin and destination first, followed by the rook's movement when castling
+/// or the captured pawn's tile for en passant, so a board can be updated by applying them in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlyInfo {
+    pub ply: Ply,
+    pub piece: Tile,
+    pub captured: Option<Tile>,
+    pub kind: PlyKind,
+    pub changes: Vec<TileChange>,
+}
+
+impl PlyInfo {
+    /// Returns true if the ply captured a piece, including en passant.
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+}
+
+impl Game {
+    /// Describes a ply that has just been executed, using the record of the tiles it overwrote.
+    pub(crate) fn ply_info(&self, record: &PlyRecord) -> PlyInfo {
+        let ply = record.ply;
+
+        let changes: Vec<TileChange> = record.undo.changes().iter()
+            .map(|&(index, before)| TileChange {
+                square: Square::from_mailbox(index),
+                before,
+                after: self.board.get_tiles()[index],
+            })
+            .collect();
+
+        let piece = changes.iter()
+            .find(|change| change.square == ply.origin)
+            .map_or(tile!(.), |change| change.before);
+
+        // Only tiles holding pieces of the other color can have been captured, the castling rook is not.
+        let captured = changes.iter()
+            .map(|change| change.before)
+            .find(|tile| matches!((piece, tile), (tile!(white), tile!(black)) | (tile!(black), tile!(white))));
+
+        let kind = match piece {
+            tile!(P) | tile!(p) if ply.promotion.is_some() => PlyKind::Promotion,
+            tile!(P) | tile!(p) if Some(ply.destination.mailbox()) == record.en_passant => PlyKind::EnPassant,
+            tile!(P) | tile!(p) if ply.origin.rank().abs_diff(ply.destination.rank()) == 2 => PlyKind::DoublePush,
+            tile!(K) | tile!(k) if ply.destination.file() == ply.origin.file() + 2 => PlyKind::KingSideCastle,
+            tile!(K) | tile!(k) if ply.destination.file() + 2 == ply.origin.file() => PlyKind::QueenSideCastle,
+            _ => PlyKind::Normal,
+        };
+
+        PlyInfo { ply, piece, captured, kind, changes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ply_info() {
+        let mut game = Game::from_fen("r3k3/1P6/8/3pP3/8/8/8/R3K2R w KQq d6 0 1").unwrap();
+
+        let info = game.clone().ply_str("e5d6").unwrap();
+        assert_eq!(info.piece, tile!(P));
+        assert_eq!(info.captured, Some(tile!(p)));
+        assert_eq!(info.kind, PlyKind::EnPassant);
+        assert_eq!(info.changes, vec![
+            TileChange { square: Square::E5, before: tile!(P), after: tile!(.) },
+            TileChange { square: Square::D6, before: tile!(.), after: tile!(P) },
+            TileChange { square: Square::D5, before: tile!(p), after: tile!(.) },
+        ]);
+
+        let info = game.clone().ply_str("e1c1").unwrap();
+        assert_eq!(info.piece, tile!(K));
+        assert_eq!(info.captured, None);
+        assert_eq!(info.kind, PlyKind::QueenSideCastle);
+        assert_eq!(info.changes[2], TileChange { square: Square::A1, before: tile!(R), after: tile!(.) });
+        assert_eq!(info.changes[3], TileChange { square: Square::D1, before: tile!(.), after: tile!(R) });
+
+        let info = game.clone().ply_str("b7a8q").unwrap();
+        assert_eq!(info.piece, tile!(P));
+        assert_eq!(info.captured, Some(tile!(r)));
+        assert_eq!(info.kind, PlyKind::Promotion);
+        assert_eq!(info.changes[1].after, tile!(Q));
+
+        let info = game.ply_str("a1a8").unwrap();
+        assert!(info.is_capture());
+        assert_eq!(info.kind, PlyKind::Normal);
+
+        let info = game.ply_str("e8d7").unwrap();
+        assert!(!info.is_capture());
+        assert_eq!(info.changes.len(), 2);
+
+        let mut game = crate::default_game();
+        assert_eq!(game.ply_san("e4").unwrap().kind, PlyKind::DoublePush);
+        assert_eq!(game.ply_san("e6").unwrap().kind, PlyKind::Normal);
+    }
+}
@@ -0,0 +1,356 @@
+use crate::board::*;
+
+use super::{Game, Ply, PlyError, PlyInfo};
+
+/// Represents an error found while reading a ply written in standard algebraic notation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SanParseError {
+    Empty,
+    /// A character that cannot appear at its place, with its character offset.
+    InvalidCharacter(char, usize),
+    /// A pawn reaching the last rank without a chosen promotion piece.
+    MissingPromotion,
+    /// No legal ply matches the notation.
+    IllegalPly,
+    /// More than one legal ply matches the notation, and the origin must be specified.
+    AmbiguousPly,
+}
+
+impl std::fmt::Display for SanParseError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SanParseError::Empty => write!(formatter, "san parsing error: empty ply"),
+            SanParseError::InvalidCharacter(char, offset) => {
+                write!(formatter, "san parsing error: invalid character '{char}' at offset {offset}")
+            },
+            SanParseError::MissingPromotion => write!(formatter, "san parsing error: a promotion piece must be chosen"),
+            SanParseError::IllegalPly => write!(formatter, "san parsing error: no legal ply matches"),
+            SanParseError::AmbiguousPly => write!(formatter, "san parsing error: more than one legal ply matches"),
+        }
+    }
+}
+
+impl std::error::Error for SanParseError {}
+
+impl Game {
+    /// Used each turn to make plys written in standard algebraic notation (Nf3, exd5, O-O, e8=Q etc.).
+    /// 
+    /// Check and checkmate suffixes, as well as annotations such as "!?", are accepted but not required.
+    /// For more details, see [`ply`].
+    /// 
+    /// [`ply`]: `Game::ply`
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// let mut game = api::default_game();
+    /// 
+    /// game.ply_san("e4").unwrap();
+    /// game.ply_san("e5").unwrap();
+    /// game.ply_san("Nf3").unwrap();
+    /// ```
+    pub fn ply_san(&mut self, san: &str) -> Result<PlyInfo, PlyError> {
+        if self.status().is_over() {
+            return Err(PlyError::GameOver);
+        }
+
+        match self.parse_san(san) {
+            Ok(ply) => self.make_ply(ply),
+            Err(e) => Err(PlyError::InvalidSan(e)),
+        }
+    }
+
+    /// Returns the legal ply (see [`Ply`]) written in standard algebraic notation, without making it.
+    pub fn parse_san(&self, san: &str) -> Result<Ply, SanParseError> {
+        let san = san.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        if san.is_empty() {
+            return Err(SanParseError::Empty);
+        }
+
+        let (king, king_origin) = match self.player {
+            Color::White => (tile!(K), Square::E1),
+            Color::Black => (tile!(k), Square::E8),
+        };
+
+        // Castling, written with either letters or zeroes.
+        let castling_destination = match san {
+            "O-O" | "0-0" => king_origin.offset(0, 2),
+            "O-O-O" | "0-0-0" => king_origin.offset(0, -2),
+            _ => None,
+        };
+
+        if let Some(destination) = castling_destination {
+            let ply = Ply { origin: king_origin, destination, promotion: None };
+
+            return match self.board.get_tile(king_origin.mailbox()) == Some(&king) && self.plys.contains(&ply) {
+                true => Ok(ply),
+                false => Err(SanParseError::IllegalPly),
+            };
+        }
+
+        let chars: Vec<char> = san.chars().collect();
+        let mut start = 0;
+        let mut end = chars.len();
+
+        // Piece letter, pawns have none.
+        let piece = match chars[0] {
+            'N' => Tile::Knight(self.player),
+            'B' => Tile::Bishop(self.player),
+            'R' => Tile::Rook(self.player),
+            'Q' => Tile::Queen(self.player),
+            'K' => Tile::King(self.player),
+            _ => Tile::Pawn(self.player),
+        };
+
+        if piece != Tile::Pawn(self.player) {
+            start += 1;
+        }
+
+        // Promotion, with or without an equals sign.
+        let promotion_piece = match chars[end - 1] {
+            'N' => Some(Tile::Knight(self.player)),
+            'B' => Some(Tile::Bishop(self.player)),
+            'R' => Some(Tile::Rook(self.player)),
+            'Q' => Some(Tile::Queen(self.player)),
+            _ => None,
+        };
+
+        let promotion = match promotion_piece {
+            Some(promotion) if piece == Tile::Pawn(self.player) => {
+                end -= 1;
+                if end > start && chars[end - 1] == '=' {
+                    end -= 1;
+                }
+                Some(promotion)
+            },
+            _ => None,
+        };
+
+        // Destination, always the last two characters.
+        if end < start + 2 {
+            // Nothing but a promotion, such as "=Q", leaves no characters before the end.
+            return match end {
+                0 => Err(SanParseError::InvalidCharacter(chars[0], 0)),
+                _ => Err(SanParseError::InvalidCharacter(chars[end - 1], end - 1)),
+            };
+        }
+
+        let destination_file = match chars[end - 2] {
+            char @ 'a'..='h' => char as i32 - 'a' as i32,
+            char => return Err(SanParseError::InvalidCharacter(char, end - 2)),
+        };
+
+        let destination_rank = match chars[end - 1] {
+            char @ '1'..='8' => char as i32 - '1' as i32,
+            char => return Err(SanParseError::InvalidCharacter(char, end - 1)),
+        };
+
+        let destination = match Square::new(destination_rank, destination_file) {
+            Some(square) => square,
+            None => return Err(SanParseError::InvalidCharacter(chars[end - 1], end - 1)),
+        };
+
+        // Disambiguation and capture marker.
+        let mut origin_file = None;
+        let mut origin_rank = None;
+
+        for (offset, char) in chars.iter().enumerate().take(end - 2).skip(start) {
+            match char {
+                'a'..='h' if origin_file.is_none() && origin_rank.is_none() => {
+                    origin_file = Some(*char as i32 - 'a' as i32)
+                },
+                '1'..='8' if origin_rank.is_none() => origin_rank = Some(*char as i32 - '1' as i32),
+                'x' if offset == end - 3 => (),
+                _ => return Err(SanParseError::InvalidCharacter(*char, offset)),
+            }
+        }
+
+        // Pawns can only leave their file by capturing, which is written with the origin file.
+        if piece == Tile::Pawn(self.player) && origin_file.is_none() {
+            origin_file = Some(destination_file);
+        }
+
+        let candidates: Vec<&Ply> = self.plys.iter()
+            .filter(|ply| ply.destination == destination)
+            .filter(|ply| self.board.get_tile(ply.origin.mailbox()) == Some(&piece))
+            .filter(|ply| origin_file.is_none_or(|file| ply.origin.file() == file))
+            .filter(|ply| origin_rank.is_none_or(|rank| ply.origin.rank() == rank))
+            .collect();
+
+        if promotion.is_none() && candidates.iter().any(|ply| ply.promotion.is_some()) {
+            return Err(SanParseError::MissingPromotion);
+        }
+
+        let mut plys = candidates.into_iter().filter(|ply| ply.promotion == promotion);
+
+        match (plys.next(), plys.next()) {
+            (Some(ply), None) => Ok(*ply),
+            (Some(_), Some(_)) => Err(SanParseError::AmbiguousPly),
+            (None, _) => Err(SanParseError::IllegalPly),
+        }
+    }
+
+    /// Returns a legal ply (see [`Ply`]) written in standard algebraic notation,
+    /// including the check or checkmate suffix.
+    /// 
+    /// The ply is made and taken back to find the suffix, so the game is borrowed mutably, 
+    /// but it is left unchanged.
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// let mut game = api::default_game();
+    /// let ply = game.parse_san("Nf3").unwrap();
+    /// 
+    /// assert_eq!(game.to_san(&ply), "Nf3");
+    /// ```
+    pub fn to_san(&mut self, ply: &Ply) -> String {
+        let mut san = String::new();
+
+        let tile = match self.board.get_tile(ply.origin.mailbox()) {
+            Some(tile) => *tile,
+            None => return san,
+        };
+
+        let is_king = matches!(tile, tile!(K) | tile!(k));
+
+        if is_king && ply.destination.file() == ply.origin.file() + 2 {
+            san.push_str("O-O");
+        } else if is_king && ply.destination.file() + 2 == ply.origin.file() {
+            san.push_str("O-O-O");
+        } else {
+            let is_capture = self.board.get_tile(ply.destination.mailbox()) != Some(&tile!(.)) 
+                || (matches!(tile, tile!(P) | tile!(p)) && Some(ply.destination.mailbox()) == self.en_passant);
+            let origin_string = ply.origin.to_string();
+
+            match tile {
+                tile!(P) | tile!(p) => if is_capture {
+                    san.push_str(&origin_string[..1]);
+                },
+                _ => {
+                    san.push_str(tile.to_string().to_uppercase().as_str());
+
+                    // Other pieces of the same kind able to reach the destination.
+                    let others: Vec<&Ply> = self.plys.iter()
+                        .filter(|other| other.destination == ply.destination && other.origin != ply.origin)
+                        .filter(|other| self.board.get_tile(other.origin.mailbox()) == Some(&tile))
+                        .collect();
+
+                    let same_file = others.iter().any(|other| other.origin.file() == ply.origin.file());
+                    let same_rank = others.iter().any(|other| other.origin.rank() == ply.origin.rank());
+
+                    if !others.is_empty() {
+                        if !same_file {
+                            san.push_str(&origin_string[..1]);
+                        } else if !same_rank {
+                            san.push_str(&origin_string[1..]);
+                        } else {
+                            san.push_str(&origin_string);
+                        }
+                    }
+                },
+            }
+
+            if is_capture {
+                san.push('x');
+            }
+
+            san.push_str(ply.destination.to_string().as_str());
+
+            if let Some(promotion) = ply.promotion {
+                san.push('=');
+                san.push_str(promotion.to_string().to_uppercase().as_str());
+            }
+        }
+
+        if !self.plys.contains(ply) {
+            return san;
+        }
+
+        // Makes the ply in place and generates the replies, to see if they are all illegal.
+        if let Ok(record) = self.execute_ply(*ply) {
+            let plys = std::mem::take(&mut self.plys);
+            self.gen_plys();
+
+            if self.is_in_check() {
+                match self.plys.is_empty() {
+                    true => san.push('#'),
+                    false => san.push('+'),
+                }
+            }
+
+            self.unexecute_ply(&record);
+            self.plys = plys;
+        }
+
+        san
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let game = Game::from_fen("r3k2r/1P1n4/8/3p4/4P3/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        let ply = |origin, destination, promotion| Ply { origin, destination, promotion };
+
+        assert_eq!(game.parse_san("exd5"), Ok(ply(Square::E4, Square::D5, None)));
+        assert_eq!(game.parse_san("e5"), Ok(ply(Square::E4, Square::E5, None)));
+        assert_eq!(game.parse_san("O-O"), Ok(ply(Square::E1, Square::G1, None)));
+        assert_eq!(game.parse_san("O-O-O+"), Ok(ply(Square::E1, Square::C1, None)));
+        assert_eq!(game.parse_san("bxa8=Q+"), Ok(ply(Square::B7, Square::A8, Some(tile!(Q)))));
+        assert_eq!(game.parse_san("b8N"), Ok(ply(Square::B7, Square::B8, Some(tile!(N)))));
+        assert_eq!(game.parse_san("Ra6"), Ok(ply(Square::A1, Square::A6, None)));
+        assert_eq!(game.parse_san("Rh1h7!?"), Ok(ply(Square::H1, Square::H7, None)));
+
+        assert_eq!(game.parse_san("b8"), Err(SanParseError::MissingPromotion));
+        assert_eq!(game.parse_san("Nf3"), Err(SanParseError::IllegalPly));
+        assert_eq!(game.parse_san("Ri1"), Err(SanParseError::InvalidCharacter('i', 1)));
+        assert_eq!(game.parse_san(""), Err(SanParseError::Empty));
+        assert_eq!(game.parse_san("=Q"), Err(SanParseError::InvalidCharacter('=', 0)));
+        assert_eq!(game.parse_san("Q"), Err(SanParseError::InvalidCharacter('Q', 0)));
+    }
+
+    #[test]
+    fn disambiguation() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/R7/4K3/R6R w - - 0 1").unwrap();
+
+        assert_eq!(game.parse_san("Rd1"), Err(SanParseError::AmbiguousPly));
+        assert_eq!(game.parse_san("Rad1").unwrap().origin, Square::A1);
+        assert_eq!(game.parse_san("R1a2").unwrap().origin, Square::A1);
+        assert_eq!(game.parse_san("R3a2").unwrap().origin, Square::A3);
+        assert_eq!(game.parse_san("Ra1d1").unwrap().origin, Square::A1);
+
+        assert_eq!(game.to_san(&game.parse_san("Rad1").unwrap()), "Rad1");
+        assert_eq!(game.to_san(&game.parse_san("Rh5").unwrap()), "Rh5");
+        assert_eq!(game.to_san(&game.parse_san("R1a2").unwrap()), "R1a2");
+        assert_eq!(game.to_san(&game.parse_san("Ra3a2").unwrap()), "R3a2");
+    }
+
+    #[test]
+    fn write() {
+        let mut game = crate::default_game();
+
+        for san in ["e4", "e5", "Nf3", "Nc6", "Bc4", "Nf6", "Ng5", "d5", "exd5", "Nxd5", "Nxf7", "Kxf7", "Qf3+"] {
+            let ply = game.parse_san(san).unwrap();
+            assert_eq!(game.to_san(&ply), san);
+            game.ply_san(san).unwrap();
+        }
+
+        let mut game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let plys = game.get_plys().clone();
+
+        assert_eq!(game.to_san(&game.parse_san("Ra8").unwrap()), "Ra8#");
+
+        // Writing a ply leaves the game unchanged.
+        assert_eq!(game.to_fen(), "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        assert_eq!(game.get_plys(), &plys);
+        assert_eq!(game.hash(), Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap().hash());
+    }
+}
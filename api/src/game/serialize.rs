@@ -0,0 +1,108 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Game;
+
+/// The version of the serialized game format, increased whenever the format changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// The serialized form of a game: the fen string of the starting position,
+/// followed by the plys made in uci notation (see [`Ply::to_uci`]).
+/// 
+/// [`Ply::to_uci`]: `super::Ply::to_uci`
+#[derive(Serialize, Deserialize)]
+struct SerializedGame {
+    version: u32,
+    fen: String,
+    plys: Vec<String>,
+}
+
+/// Games are serialized as a versioned record of the starting position and the plys made, 
+/// and deserialized by replaying the plys. 
+/// 
+/// Plys that have been undone and claimed draws are not kept.
+/// 
+/// # Examples
+/// 
+/// ```
+/// let mut game = api::default_game();
+/// game.ply_str("e2e4").unwrap();
+/// 
+/// let json = serde_json::to_string(&game).unwrap();
+/// assert_eq!(json, r#"{"version":1,"fen":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","plys":["e2e4"]}"#);
+/// 
+/// let copy: api::Game = serde_json::from_str(&json).unwrap();
+/// assert_eq!(copy.history(), game.history());
+/// ```
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedGame {
+            version: FORMAT_VERSION,
+            fen: self.start_fen.clone(),
+            plys: self.history().iter().map(|ply| ply.to_uci()).collect(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Game {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedGame::deserialize(deserializer)?;
+
+        if serialized.version != FORMAT_VERSION {
+            return Err(de::Error::custom(format!("unsupported game format version {}", serialized.version)));
+        }
+
+        let game = Game::from_fen(&serialized.fen).map_err(de::Error::custom)?;
+
+        Game::read_uci_moves(game, serialized.plys.iter().map(String::as_str)).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn round_trip() {
+        let mut game = Game::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 3 40").unwrap();
+
+        for ply in ["b7b8q", "e8d7", "b8b5"] {
+            game.ply_str(ply).unwrap();
+        }
+
+        let json = serde_json::to_string(&game).unwrap();
+        let copy: Game = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(copy, game);
+        assert_eq!(copy.history(), game.history());
+        assert_eq!(copy.to_pgn(), game.to_pgn());
+
+        // Undoing in the copy goes back to the starting position.
+        let mut copy = copy;
+        while copy.undo().is_some() {}
+        assert_eq!(copy.to_fen(), "4k3/1P6/8/8/8/8/8/4K3 w - - 3 40");
+    }
+
+    #[test]
+    fn invalid() {
+        let fen = r#""fen":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1""#;
+
+        assert!(serde_json::from_str::<Game>(&format!(r#"{{"version":2,{fen},"plys":[]}}"#)).is_err());
+        assert!(serde_json::from_str::<Game>(&format!(r#"{{"version":1,{fen},"plys":["e2e5"]}}"#)).is_err());
+        assert!(serde_json::from_str::<Game>(r#"{"version":1,"fen":"8/8/8/8/8/8/8/8 w - - 0 1","plys":[]}"#).is_err());
+    }
+
+    #[test]
+    fn types() {
+        let ply = Ply { origin: Square::E7, destination: Square::E8, promotion: Some(Tile::Queen(Color::White)) };
+        let json = serde_json::to_string(&ply).unwrap();
+
+        assert_eq!(serde_json::from_str::<Ply>(&json).unwrap(), ply);
+
+        let pos = Pos { rank: 3, file: 4 };
+        assert_eq!(serde_json::to_string(&pos).unwrap(), r#"{"rank":3,"file":4}"#);
+        assert_eq!(serde_json::from_str::<Pos>(r#"{"rank":3,"file":4}"#).unwrap(), pos);
+
+        assert_eq!(serde_json::to_string(&Tile::Knight(Color::Black)).unwrap(), r#"{"Knight":"Black"}"#);
+        assert_eq!(serde_json::from_str::<Color>(r#""White""#).unwrap(), Color::White);
+    }
+}
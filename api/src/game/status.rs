@@ -0,0 +1,404 @@
+use crate::board::*;
+
+use super::{CastlingRights, Game};
+
+/// Represents the reasons a game can end in a draw, apart from stalemate (see [`GameStatus`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrawReason {
+    /// 50 moves by each player without a pawn move or capture, claimed by a player.
+    FiftyMoveRule,
+    /// 75 moves by each player without a pawn move or capture.
+    SeventyFiveMoveRule,
+    /// The same position occurring three times, claimed by a player.
+    ThreefoldRepetition,
+    /// The same position occurring five times.
+    FivefoldRepetition,
+    /// Neither player has enough pieces left to checkmate.
+    InsufficientMaterial,
+    /// No sequence of legal plys can lead to checkmate.
+    DeadPosition,
+}
+
+impl std::fmt::Display for DrawReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let string = match self {
+            DrawReason::FiftyMoveRule => "fifty-move rule",
+            DrawReason::SeventyFiveMoveRule => "seventy-five-move rule",
+            DrawReason::ThreefoldRepetition => "threefold repetition",
+            DrawReason::FivefoldRepetition => "fivefold repetition",
+            DrawReason::InsufficientMaterial => "insufficient material",
+            DrawReason::DeadPosition => "dead position",
+        };
+
+        write!(f, "{string}")
+    }
+}
+
+/// Represents everything that makes two positions the same when testing for repetition:
+/// the board, the player to move, castling rights and a possible en passant capture.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Position {
+    board: Board,
+    player: Color,
+    castling: CastlingRights,
+    en_passant: Option<usize>,
+}
+
+/// Represents the state of a game, either ongoing or ended in one of the ways chess games end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate { winner: Color },
+    Stalemate,
+    Draw(DrawReason),
+}
+
+impl GameStatus {
+    /// Returns true if no more plys can be made.
+    pub fn is_over(&self) -> bool {
+        !matches!(self, GameStatus::Ongoing)
+    }
+}
+
+impl std::fmt::Display for GameStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GameStatus::Ongoing => write!(f, "Ongoing"),
+            GameStatus::Checkmate { winner } => write!(f, "Checkmate, {winner} wins"),
+            GameStatus::Stalemate => write!(f, "Stalemate"),
+            GameStatus::Draw(reason) => write!(f, "Draw by {reason}"),
+        }
+    }
+}
+
+impl Game {
+    /// Returns the current state of the game (see [`GameStatus`]).
+    /// 
+    /// A player without legal plys is checkmated if their king is in check, otherwise it is stalemate.
+    /// Draws by insufficient material, dead positions, 
+    /// the seventy-five-move rule and fivefold repetition are automatic, 
+    /// while draws by the fifty-move rule and threefold repetition must be claimed with [`claim_draw`].
+    /// 
+    /// [`claim_draw`]: `Game::claim_draw`
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// use api::GameStatus;
+    /// 
+    /// let game = api::fen_game("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    /// 
+    /// assert_eq!(game.status(), GameStatus::Stalemate);
+    /// ```
+    pub fn status(&self) -> GameStatus {
+        if self.plys.is_empty() {
+            return match self.is_in_check() {
+                true => GameStatus::Checkmate { winner: self.opponent() },
+                false => GameStatus::Stalemate,
+            };
+        }
+
+        if let Some(reason) = self.claimed_draw {
+            return GameStatus::Draw(reason);
+        }
+
+        if self.board.is_insufficient_material() {
+            return GameStatus::Draw(DrawReason::InsufficientMaterial);
+        }
+
+        if self.is_dead_position() {
+            return GameStatus::Draw(DrawReason::DeadPosition);
+        }
+
+        if self.ply_clock >= 150 {
+            return GameStatus::Draw(DrawReason::SeventyFiveMoveRule);
+        }
+
+        if self.repetition_count() >= 5 {
+            return GameStatus::Draw(DrawReason::FivefoldRepetition);
+        }
+
+        GameStatus::Ongoing
+    }
+
+    /// Returns the reason the current player could claim a draw for, if any.
+    pub fn can_claim_draw(&self) -> Option<DrawReason> {
+        if self.status().is_over() {
+            return None;
+        }
+
+        if self.ply_clock >= 100 {
+            return Some(DrawReason::FiftyMoveRule);
+        }
+
+        if self.repetition_count() >= 3 {
+            return Some(DrawReason::ThreefoldRepetition);
+        }
+
+        None
+    }
+
+    /// Returns the number of times the current position has occurred in the game, including now.
+    /// 
+    /// Positions are the same if the same pieces stand on the same tiles, the same player is to move,
+    /// and the same castling and en passant captures are possible. They are compared by hash (see [`hash`]).
+    /// 
+    /// [`hash`]: `Game::hash`
+    pub fn repetition_count(&self) -> usize {
+        match self.positions.last() {
+            Some(current) => self.positions.iter().filter(|position| *position == current).count(),
+            None => 0,
+        }
+    }
+
+    /// Returns true if only kings and pawns remain, the pawns are locked, 
+    /// and neither king can ever capture a pawn. No player is then able to give check, 
+    /// so checkmate is impossible.
+    /// 
+    /// A pawn is locked if the tile in front of it holds a pawn and it has nothing to capture.
+    /// Kings are assumed to be able to capture any pawn they reach which is not protected by another pawn.
+    pub(crate) fn is_dead_position(&self) -> bool {
+        let tiles = self.board.get_tiles();
+        let tile_at = |index: usize, delta: isize| tiles.get((index as isize + delta) as usize);
+
+        let mut has_pawns = false;
+
+        for (index, tile) in tiles.iter().enumerate() {
+            let (forward, capture_delta, enemy_pawn) = match tile {
+                tile!(P) => (10, [9, 11], tile!(p)),
+                tile!(p) => (-10, [-9, -11], tile!(P)),
+                tile!(K) | tile!(k) | tile!(.) | tile!(_) => continue,
+                _ => return false,
+            };
+
+            has_pawns = true;
+
+            if !matches!(tile_at(index, forward), Some(tile!(P) | tile!(p))) {
+                return false;
+            }
+
+            if capture_delta.iter().any(|&delta| tile_at(index, delta) == Some(&enemy_pawn)) {
+                return false;
+            }
+        }
+
+        if !has_pawns || self.is_in_check() {
+            return false;
+        }
+
+        // A tile is attacked by a pawn if a pawn of that color stands diagonally behind it.
+        let is_pawn_attacked = |index: usize, by: Color| match by {
+            Color::White => [-9, -11].iter().any(|&delta| tile_at(index, delta) == Some(&tile!(P))),
+            Color::Black => [9, 11].iter().any(|&delta| tile_at(index, delta) == Some(&tile!(p))),
+        };
+
+        let king_delta = [-11, -10, -9, -1, 1, 9, 10, 11];
+
+        for (king, enemy, enemy_pawn) in [(tile!(K), Color::Black, tile!(p)), (tile!(k), Color::White, tile!(P))] {
+            let Some(origin) = tiles.iter().position(|tile| *tile == king) else {
+                return false;
+            };
+
+            // Find every tile the king can walk to without passing a pawn or stepping into check.
+            let mut reachable = vec![origin];
+            let mut visited = [false; 120];
+            visited[origin] = true;
+
+            while let Some(index) = reachable.pop() {
+                for delta in king_delta {
+                    let next = (index as isize + delta) as usize;
+
+                    match tiles.get(next) {
+                        Some(tile) if *tile == enemy_pawn && !is_pawn_attacked(next, enemy) => return false,
+                        Some(tile!(.) | tile!(K) | tile!(k)) if !visited[next] && !is_pawn_attacked(next, enemy) => {
+                            visited[next] = true;
+                            reachable.push(next);
+                        },
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns the current position, used to compare games.
+    pub(crate) fn position(&self) -> Position {
+        Position {
+            board: self.board.clone(),
+            player: self.player,
+            castling: self.castling,
+            en_passant: self.en_passant_capture(),
+        }
+    }
+
+    /// Returns the en passant target if the capture is actually possible, 
+    /// only then does it matter when comparing positions.
+    pub(crate) fn en_passant_capture(&self) -> Option<usize> {
+        self.en_passant.filter(|target| self.plys.iter().any(|ply| {
+            ply.destination.mailbox() == *target && matches!(self.board.get_tile(ply.origin.mailbox()), Some(tile!(P) | tile!(p)))
+        }))
+    }
+
+    /// Ends the game in a draw if the current player is allowed to claim one, 
+    /// and returns the reason (see [`DrawReason`]).
+    /// 
+    /// Returns None and leaves the game unchanged if no draw can be claimed.
+    pub fn claim_draw(&mut self) -> Option<DrawReason> {
+        let reason = self.can_claim_draw()?;
+
+        self.claimed_draw = Some(reason);
+
+        Some(reason)
+    }
+
+    /// Returns true if the current player is checkmated.
+    pub fn is_checkmate(&self) -> bool {
+        matches!(self.status(), GameStatus::Checkmate { .. })
+    }
+
+    /// Returns the color of the player waiting for their turn.
+    pub(crate) fn opponent(&self) -> Color {
+        match self.player {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkmate() {
+        let mut game = crate::default_game();
+
+        for ply in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            assert_eq!(game.status(), GameStatus::Ongoing);
+            game.ply_str(ply).unwrap();
+        }
+
+        assert_eq!(game.status(), GameStatus::Checkmate { winner: Color::Black });
+        assert!(game.is_checkmate());
+        assert!(matches!(game.ply_str("e2e4"), Err(crate::PlyError::GameOver)));
+    }
+
+    #[test]
+    fn move_rules() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 98 60").unwrap();
+
+        game.ply_str("a1a2").unwrap();
+
+        assert_eq!(game.get_ply_clock(), 99);
+        assert_eq!(game.can_claim_draw(), None);
+
+        game.ply_str("e8e7").unwrap();
+
+        assert_eq!(game.get_ply_clock(), 100);
+        assert_eq!(game.get_move_clock(), 61);
+        assert_eq!(game.claim_draw(), Some(DrawReason::FiftyMoveRule));
+        assert_eq!(game.status(), GameStatus::Draw(DrawReason::FiftyMoveRule));
+
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 149 80").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Ongoing);
+
+        game.ply_str("a1a2").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Draw(DrawReason::SeventyFiveMoveRule));
+    }
+
+    #[test]
+    fn clocks_reset() {
+        let mut game = Game::from_fen("4k3/4p3/8/8/8/8/8/R3K3 w - - 10 1").unwrap();
+
+        game.ply_str("a1a7").unwrap();
+        assert_eq!(game.get_ply_clock(), 11);
+
+        game.ply_str("e7e5").unwrap();
+        assert_eq!(game.get_ply_clock(), 0);
+        assert_eq!(game.get_move_clock(), 2);
+    }
+
+    #[test]
+    fn repetition() {
+        let mut game = crate::default_game();
+
+        assert_eq!(game.repetition_count(), 1);
+
+        for _ in 0..2 {
+            for ply in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+                game.ply_str(ply).unwrap();
+            }
+        }
+
+        assert_eq!(game.repetition_count(), 3);
+        assert_eq!(game.can_claim_draw(), Some(DrawReason::ThreefoldRepetition));
+        assert_eq!(game.status(), GameStatus::Ongoing);
+
+        for _ in 0..2 {
+            for ply in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+                game.ply_str(ply).unwrap();
+            }
+        }
+
+        assert_eq!(game.repetition_count(), 5);
+        assert_eq!(game.status(), GameStatus::Draw(DrawReason::FivefoldRepetition));
+    }
+
+    #[test]
+    fn repetition_rights() {
+        // The first position had castling rights, the repeated ones do not.
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+
+        for ply in ["h1h2", "e8d8", "h2h1", "d8e8", "h1h2", "e8d8", "h2h1", "d8e8"] {
+            game.ply_str(ply).unwrap();
+        }
+
+        assert_eq!(game.repetition_count(), 2);
+    }
+
+    #[test]
+    fn insufficient_material() {
+        let mut game = Game::from_fen("4k3/2n5/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Ongoing);
+
+        game.ply_str("a1a7").unwrap();
+        game.ply_str("e8d8").unwrap();
+        game.ply_str("a7c7").unwrap();
+        game.ply_str("d8c7").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Draw(DrawReason::InsufficientMaterial));
+        assert!(game.ply_str("e1e2").is_err());
+    }
+
+    #[test]
+    fn dead_position() {
+        // The pawn chain cannot be crossed by either king.
+        let game = Game::from_fen("8/4k3/8/1p1p1p1p/pP1P1P1P/P7/4K3/8 w - - 0 1").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Draw(DrawReason::DeadPosition));
+
+        // The black king can reach the unprotected pawn on h4 through the gap on the h-file.
+        let game = Game::from_fen("8/4k3/8/1p1p1p2/pP1P1P1P/P7/4K3/8 w - - 0 1").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Ongoing);
+
+        // The pawns are not locked.
+        let game = Game::from_fen("8/4k3/8/1p1p1p1p/pP1P1P1P/8/P3K3/8 w - - 0 1").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Ongoing);
+    }
+
+    #[test]
+    fn stalemate() {
+        let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+
+        assert_eq!(game.status(), GameStatus::Stalemate);
+        assert!(!game.is_checkmate());
+    }
+}
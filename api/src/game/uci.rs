@@ -0,0 +1,207 @@
+use crate::board::*;
+
+use super::{FenParseError, Game, Ply, PlyError};
+
+/// Represents an error found while reading a uci position command, see [`Game::from_uci_position`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum UciParseError {
+    /// The command does not start with "startpos" or "fen", or has something other than "moves" after the position.
+    InvalidCommand(String),
+    InvalidFen(FenParseError),
+    /// A ply in the move list that cannot be made, with its index in the list.
+    InvalidPly(usize, PlyError),
+}
+
+impl std::fmt::Display for UciParseError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UciParseError::InvalidCommand(token) => write!(formatter, "uci parsing error: unexpected '{token}'"),
+            UciParseError::InvalidFen(e) => write!(formatter, "uci parsing error: {e}"),
+            UciParseError::InvalidPly(index, e) => write!(formatter, "uci parsing error: ply {}: {e}", index + 1),
+        }
+    }
+}
+
+impl std::error::Error for UciParseError {}
+
+impl Ply {
+    /// Returns the ply in the long algebraic notation used by the universal chess interface (uci).
+    /// 
+    /// Positions are written in lowercase without a space, followed by the letter of the promotion piece if any
+    /// (e2e4, e7e8q etc.). Castling is written as the king's movement (e1g1 etc.).
+    pub fn to_uci(&self) -> String {
+        let mut uci = self.origin.to_string();
+
+        uci.push_str(self.destination.to_string().as_str());
+
+        if let Some(promotion) = self.promotion {
+            uci.push_str(promotion.to_string().to_lowercase().as_str());
+        }
+
+        uci
+    }
+}
+
+impl Game {
+    /// Returns the legal ply (see [`Ply`]) written in uci long algebraic notation, without making it.
+    /// 
+    /// See [`Ply::to_uci`] for the format.
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// let game = api::default_game();
+    /// let ply = game.parse_uci_move("g1f3").unwrap();
+    /// 
+    /// assert_eq!(ply.to_uci(), "g1f3");
+    /// ```
+    pub fn parse_uci_move(&self, uci: &str) -> Result<Ply, PlyError> {
+        let chars: Vec<char> = uci.chars().collect();
+
+        if chars.len() != 4 && chars.len() != 5 {
+            return Err(PlyError::InvalidPly);
+        }
+
+        let mut squares = [Square::A1; 2];
+
+        for (square, chars) in squares.iter_mut().zip(chars.chunks(2)) {
+            let file = match chars[0] {
+                char @ 'a'..='h' => char as i32 - 'a' as i32,
+                _ => return Err(PlyError::InvalidPly),
+            };
+
+            let rank = match chars[1] {
+                char @ '1'..='8' => char as i32 - '1' as i32,
+                _ => return Err(PlyError::InvalidPly),
+            };
+
+            *square = Square::new(rank, file).ok_or(PlyError::InvalidPly)?;
+        }
+
+        let promotion = match chars.get(4) {
+            Some('q') => Some(Tile::Queen(self.player)),
+            Some('r') => Some(Tile::Rook(self.player)),
+            Some('b') => Some(Tile::Bishop(self.player)),
+            Some('n') => Some(Tile::Knight(self.player)),
+            Some(_) => return Err(PlyError::InvalidPly),
+            None => None,
+        };
+
+        let ply = Ply {
+            origin: squares[0],
+            destination: squares[1],
+            promotion,
+        };
+
+        self.check_ply(&ply)?;
+
+        Ok(ply)
+    }
+
+    /// Returns a game from the arguments of a uci position command, 
+    /// "startpos" or "fen" followed by a fen string, and optionally "moves" followed by plys in uci notation.
+    /// The command name itself may be included.
+    /// 
+    /// # Examples
+    /// 
+    /// Basic use:
+    /// ```
+    /// let game = api::Game::from_uci_position("startpos moves e2e4 e7e5").unwrap();
+    /// 
+    /// assert_eq!(game.to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2");
+    /// ```
+    pub fn from_uci_position(command: &str) -> Result<Self, UciParseError> {
+        let mut tokens = command.split_whitespace().peekable();
+
+        tokens.next_if_eq(&"position");
+
+        let game = match tokens.next() {
+            Some("startpos") => {
+                match tokens.next() {
+                    Some("moves") | None => (),
+                    Some(token) => return Err(UciParseError::InvalidCommand(token.to_string())),
+                }
+
+                crate::default_game()
+            },
+            Some("fen") => {
+                // Reading the fen string also consumes "moves", if present.
+                let fen: Vec<&str> = tokens.by_ref().take_while(|token| *token != "moves").collect();
+
+                Game::from_fen(&fen.join(" ")).map_err(UciParseError::InvalidFen)?
+            },
+            Some(token) => return Err(UciParseError::InvalidCommand(token.to_string())),
+            None => return Err(UciParseError::InvalidCommand(String::new())),
+        };
+
+        Self::read_uci_moves(game, tokens)
+    }
+
+    pub(crate) fn read_uci_moves<'a>(mut game: Game, moves: impl Iterator<Item = &'a str>) -> Result<Self, UciParseError> {
+        for (index, uci) in moves.enumerate() {
+            let ply = game.parse_uci_move(uci).map_err(|e| UciParseError::InvalidPly(index, e))?;
+
+            game.make_ply(ply).map_err(|e| UciParseError::InvalidPly(index, e))?;
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_uci() {
+        let game = Game::from_fen("4k3/1P6/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+
+        let mut ucis: Vec<String> = game.get_plys().iter().map(|ply| ply.to_uci()).collect();
+        ucis.sort();
+
+        assert!(ucis.contains(&"e1g1".to_string()));
+        assert!(ucis.contains(&"b7b8q".to_string()));
+        assert!(ucis.contains(&"b7b8n".to_string()));
+
+        for uci in ucis {
+            assert_eq!(game.parse_uci_move(&uci).unwrap().to_uci(), uci);
+        }
+    }
+
+    #[test]
+    fn parse_uci_move() {
+        let game = Game::from_fen("4k3/1P6/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+
+        assert_eq!(game.parse_uci_move("b7b8"), Err(PlyError::MissingPromotion));
+        assert_eq!(game.parse_uci_move("b7b8k"), Err(PlyError::InvalidPly));
+        assert_eq!(game.parse_uci_move("E1G1"), Err(PlyError::InvalidPly));
+        assert_eq!(game.parse_uci_move("e1e3"), Err(PlyError::IllegalDestination(Square::E1, Square::E3)));
+        assert_eq!(game.parse_uci_move("e1"), Err(PlyError::InvalidPly));
+    }
+
+    #[test]
+    fn from_uci_position() {
+        let game = Game::from_uci_position("position startpos").unwrap();
+        assert_eq!(game.to_fen(), crate::DEFAULT_FEN);
+
+        let game = Game::from_uci_position("startpos moves e2e4 c7c5 g1f3").unwrap();
+        assert_eq!(game.to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+
+        let game = Game::from_uci_position("fen 4k3/1P6/8/8/8/8/8/4K2R w K - 0 1 moves e1g1 e8d7 b7b8q").unwrap();
+        assert_eq!(game.to_fen(), "1Q6/3k4/8/8/8/8/8/5RK1 b - - 0 2");
+
+        let game = Game::from_uci_position("fen 4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+
+        assert_eq!(
+            Game::from_uci_position("startpos moves e2e4 e2e4").unwrap_err(),
+            UciParseError::InvalidPly(1, PlyError::NoPiece(Square::E2))
+        );
+        assert_eq!(
+            Game::from_uci_position("startpos e2e4").unwrap_err(),
+            UciParseError::InvalidCommand("e2e4".to_string())
+        );
+        assert!(matches!(Game::from_uci_position("fen 8/8 w - - 0 1"), Err(UciParseError::InvalidFen(_))));
+    }
+}
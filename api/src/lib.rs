@@ -8,15 +8,43 @@
 //! 
 //! To get the current board state, use [`Game::get_board_2d`].
 //! 
+//! To list the pieces on the board, use [`Game::pieces`], [`Game::pieces_of`] and [`Game::king_square`],
+//! and to count material, use [`Game::material`].
+//! 
 //! To get the current player, use [`Game::get_player`].
 //! 
 //! To get all valid plys for the current player, use [`Game::get_plys`].
 //! 
-//! To get all valid plys for a specific piece (based on the current player), use [`Game::get_plys_from_pos`].
+//! To get all valid plys for a specific piece (based on the current player), use [`Game::get_plys_from_pos`],
+//! or [`Game::get_plys_from_square`] with a [`Square`].
+//! 
+//! To make a ply, use [`Game::ply`], or [`Game::ply_san`] for standard algebraic notation.
+//! The ply made is described by a [`PlyInfo`], with the moved and captured pieces and every changed tile.
+//! 
+//! To take back plys, use [`Game::undo`] and [`Game::redo`], and to list the plys made, use [`Game::history`].
 //! 
-//! To make a ply, use [`Game::ply`].
+//! To get a 64-bit hash of the current position, for transposition tables and caches, use [`Game::hash`].
+//! 
+//! To verify ply generation against published node counts, use [`Game::perft`] and [`Game::divide`].
 //! 
 //! To test for checkmate, use [`Game::is_checkmate`].
+//! 
+//! To test if the game has ended, and how, use [`Game::status`].
+//! 
+//! To find checks and attacked squares, use [`Game::is_in_check`], [`Game::checkers`], [`Game::attackers_of`] and [`Game::is_square_attacked`].
+//! 
+//! To save the current position as a fen string, use [`Game::to_fen`].
+//! 
+//! To set up a custom position piece by piece, use a [`PositionBuilder`].
+//! 
+//! To communicate with chess engines, use [`Ply::to_uci`], [`Game::parse_uci_move`] and [`Game::from_uci_position`].
+//! 
+//! To load games from pgn, use [`Pgn::parse`] or [`Game::from_pgn`], and to save them, use [`Game::to_pgn`].
+//! 
+//! Every error type implements [`std::error::Error`] and converts into a [`ChessError`], for use with the `?` operator.
+//! 
+//! With the `serde` feature enabled, games, plys and the board types can be serialized with serde.
+//! Games are stored as their starting fen string followed by the plys made, in uci notation.
 
 mod game;
 pub use crate::game::*;
@@ -24,6 +52,12 @@ pub use crate::game::*;
 mod board;
 pub use crate::board::*;
 
+mod error;
+pub use crate::error::*;
+
+/// The fen string of the beginning position in chess.
+const DEFAULT_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
 /// Returns a [`Game`] object representing the beginning position in chess.
 /// 
 /// If you wish to start a game from a specific position, 
@@ -36,7 +70,7 @@ pub use crate::board::*;
 /// let mut game = api::default_game();
 /// ```
 pub fn default_game() -> Game {
-    match Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1") {
+    match Game::from_fen(DEFAULT_FEN) {
         Ok(x) => x,
         Err(_) => panic!(),
     }
@@ -44,7 +78,8 @@ pub fn default_game() -> Game {
 
 /// Returns a [`Game`] object representing an arbitrary position in chess.
 /// 
-/// If the fen string is incorrect in some way, it will return. 
+/// If the fen string is incorrect in some way, it will return an error (see [`FenParseError`])
+/// pointing at the problem.
 /// 
 /// # Examples
 /// 
@@ -53,9 +88,17 @@ pub fn default_game() -> Game {
 /// let game = api::fen_game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
 /// ```
 /// 
-/// Empty board:
+/// Only kings:
 /// ```
-/// let game = api::fen_game("8/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+/// let game = api::fen_game("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+/// ```
+/// 
+/// Invalid position:
+/// ```
+/// // Black has no king.
+/// let error = api::fen_game("8/8/8/8/8/8/8/4K3 w - - 0 1").unwrap_err();
+/// 
+/// assert_eq!(error.offset(), 0);
 /// ```
 pub fn fen_game(fen: &str) -> Result<Game, FenParseError> {
     Game::from_fen(fen)
@@ -63,6 +106,7 @@ pub fn fen_game(fen: &str) -> Result<Game, FenParseError> {
 
 #[cfg(test)]
 mod tests {
+    #[allow(unused_imports)]
     use super::*;
 
     #[test]
@@ -1,5 +1,5 @@
 use api::*;
-use std::io::{self, stdin};
+use std::io::stdin;
 
 fn main() {
     // Preferred method for creating a new game.
@@ -13,32 +13,25 @@ fn main() {
         // Use Game::get_tile() to get the current state of a single tile.
         let board = game.get_board_2d();
 
-        for rank in (0..8 as usize).rev() {
+        for row in board.iter().rev() {
             board_string.push_str("| ");
-            for file in 0..8 as usize {
-                // Every tile is either a piece with a color, empty,
-                // or a sentinel value that should be ignored.
-                board_string.push_str(match board[rank][file] {
-                    Tile::Pawn(Color::White)   => "P ",
-                    Tile::Bishop(Color::White) => "B ", 
-                    Tile::Knight(Color::White) => "N ", 
-                    Tile::Rook(Color::White)   => "R ", 
-                    Tile::Queen(Color::White)  => "Q ", 
-                    Tile::King(Color::White)   => "K ", 
-                    Tile::Pawn(Color::Black)   => "p ", 
-                    Tile::Bishop(Color::Black) => "b ", 
-                    Tile::Knight(Color::Black) => "n ", 
-                    Tile::Rook(Color::Black)   => "r ", 
-                    Tile::Queen(Color::Black)  => "q ", 
-                    Tile::King(Color::Black)   => "k ", 
-                    Tile::Empty => ". ",
-                    _ => "",
-                });
+            for tile in row {
+                // Tiles are written as fen letters, with '.' for empty tiles.
+                board_string.push_str(&format!("{tile} "));
             }
             board_string.push_str("|\n");
         }
         println!("{board_string}");
 
+        // Use Game::pieces(), Game::pieces_of() and Game::material() to inspect the pieces on the board.
+        println!("Material: White {}, Black {}", game.material(Color::White), game.material(Color::Black));
+
+        // Use Game::status() to find out if and how the game has ended.
+        if game.status().is_over() {
+            println!("{}", game.status());
+            break;
+        }
+
         // Use Game::get_player() to get the current player.
         println!("{}'s Turn", game.get_player());
 
@@ -52,14 +45,20 @@ fn main() {
             println!("Enter Ply:");
             let mut input = String::new();
             match stdin().read_line(&mut input) {
+                // End of input.
+                Ok(0) => return,
                 Ok(_) => (),
                 Err(_) => continue 'input,
             }
 
             // Use Game::ply_str() Game::ply() to make plys with strings and positions respectively.
+            // The error explains why a ply was rejected.
             match game.ply_str(input.as_str()) {
                 Ok(_) => break 'input,
-                Err(_) => continue 'input,
+                Err(e) => {
+                    println!("{e}");
+                    continue 'input;
+                },
             }
         }
     }